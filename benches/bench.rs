@@ -5,9 +5,7 @@ extern crate criterion;
 
 use std::ops::Div;
 
-use ark_bls12_377::Bls12_377;
 use ark_bls12_381::Bls12_381;
-use ark_bn254::Bn254;
 use ark_ec::PairingEngine;
 use ark_poly::univariate::DensePolynomial;
 use ark_poly::UVPolynomial;
@@ -35,7 +33,8 @@ macro_rules! param_gen_bench {
         let bench_str = format!("curve: {}, dimension: {}", $disc, $dim,);
         $bencher.bench_function(bench_str, move |b| {
             b.iter(|| {
-                let _ = StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng);
+                let _ = StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng)
+                    .unwrap();
             });
         });
     };
@@ -61,38 +60,54 @@ fn param_gen(c: &mut Criterion) {
 macro_rules! single_commit_and_open_bench {
     ($engine: tt, $dim: expr, $bencher: tt, $disc: tt) => {
         let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
-        let srs = StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng);
+        let srs =
+            StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
         let prover_param: ProverParam<$engine, $dim> = (&srs).into();
         let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
         let message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
             .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
             .collect();
 
-        let commitment = Commitment::<$engine, $dim>::commit(&prover_param, &message);
+        let commitment = Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap();
         let prover_param_clone = prover_param.clone();
         let message_clone = message.clone();
         let bench_str = format!("curve {}, commit to {} messages", $disc, $dim,);
         $bencher.bench_function(bench_str, move |b| {
             b.iter(|| {
-                let _ = Commitment::<$engine, $dim>::commit(&prover_param_clone, &message_clone);
+                let _ = Commitment::<$engine, $dim>::commit(&prover_param_clone, &message_clone)
+                    .unwrap();
+            });
+        });
+
+        let prover_param_clone = prover_param.clone();
+        let message_clone = message.clone();
+        let bench_str = format!("curve {}, dim {}, open all messages", $disc, $dim,);
+        $bencher.bench_function(bench_str, move |b| {
+            b.iter(|| {
+                let _ = Commitment::<$engine, $dim>::open_all(&prover_param_clone, &message_clone)
+                    .unwrap();
             });
         });
 
         let pos = (rng.next_u32() % $dim) as usize;
         let m = message[pos];
-        let witness = Commitment::<$engine, $dim>::open(&prover_param, &message, pos);
+        let witness = Commitment::<$engine, $dim>::open(&prover_param, &message, pos).unwrap();
 
         let bench_str = format!("curve {}, dim {}, open 1 message", $disc, $dim,);
         $bencher.bench_function(bench_str, move |b| {
             b.iter(|| {
                 let pos = (rng.next_u32() % $dim) as usize;
-                let _ = Commitment::<$engine, $dim>::open(&prover_param, &message, pos);
+                let _ = Commitment::<$engine, $dim>::open(&prover_param, &message, pos).unwrap();
             });
         });
 
         let bench_str = format!("curve {}, dim {}, verify 1 message", $disc, $dim,);
         $bencher.bench_function(bench_str, move |b| {
-            b.iter(|| assert!(commitment.verify(&verifier_param, &m, pos, &witness)));
+            b.iter(|| {
+                assert!(commitment
+                    .verify(&verifier_param, &m, pos, &witness)
+                    .unwrap())
+            });
         });
     };
 }
@@ -130,7 +145,7 @@ where
     let degree = dim;
     let mut bench_group = c.benchmark_group(format!("kzg dim {}", degree));
 
-    let bench_str = format!("setup");
+    let bench_str = "setup";
     bench_group.bench_function(bench_str, move |b| {
         b.iter(|| {
             let _ = KZG10::<E, P>::setup(degree, false, &mut rng).unwrap();
@@ -139,11 +154,11 @@ where
 
     let mut rng = test_rng();
     let pp = KZG10::<E, P>::setup(degree, false, &mut rng).unwrap();
-    let (ck, vk) = trim(&pp, degree);
+    let (ck, vk) = trim(&pp, degree).unwrap();
     let p = P::rand(degree, &mut rng);
     let point = E::Fr::rand(&mut rng);
 
-    let bench_str = format!("commit");
+    let bench_str = "commit";
     let ck_clone = ck.clone();
     let p_clone = p.clone();
     bench_group.bench_function(bench_str, move |b| {
@@ -155,7 +170,7 @@ where
     let p_clone = p.clone();
     let (comm, rand) = KZG10::<E, P>::commit(&ck, &p_clone, None, None).unwrap();
 
-    let bench_str = format!("evaluate");
+    let bench_str = "evaluate";
     bench_group.bench_function(bench_str, move |b| {
         b.iter(|| {
             let _ = p_clone.evaluate(&point);
@@ -164,17 +179,17 @@ where
 
     let p_clone = p.clone();
     let value = p.evaluate(&point);
-    let proof = open(&ck, &p_clone, point, &rand);
+    let proof = open(&ck, &p_clone, point, &rand).unwrap();
 
-    let bench_str = format!("prove");
+    let bench_str = "prove";
     let p_clone = p.clone();
     bench_group.bench_function(bench_str, move |b| {
         b.iter(|| {
-            let _ = open(&ck, &p_clone, point, &rand);
+            let _ = open(&ck, &p_clone, point, &rand).unwrap();
         })
     });
 
-    let bench_str = format!("verify");
+    let bench_str = "verify";
     bench_group.bench_function(bench_str, move |b| {
         b.iter(|| {
             assert!(
@@ -222,7 +237,7 @@ where
     // let bench_str = "pps_setup";
     // ttt.bench_function(bench_str, move |b| {
     //     b.iter(|| {
-    //         let _ = StructuredReferenceString::<E, M>::new_srs_for_testing(&mut rng);
+    //         let _ = StructuredReferenceString::<E, M>::new_srs_for_testing(&mut rng).unwrap();
     //     });
     // });
 
@@ -231,7 +246,7 @@ where
     // =================
     let mut rng = test_rng();
     let kzg_pp = KZG10::<E, P>::setup(dim, false, &mut rng).unwrap();
-    let (ck, vk) = trim(&kzg_pp, dim);
+    let (ck, vk) = trim(&kzg_pp, dim).unwrap();
     let p = P::rand(dim, &mut rng);
     let point = E::Fr::rand(&mut rng);
 
@@ -243,7 +258,7 @@ where
             let _ = KZG10::<E, P>::commit(&ck_clone, &p_clone, None, None).unwrap();
         })
     });
-    let srs = StructuredReferenceString::<E, M>::new_srs_for_testing(&mut rng);
+    let srs = StructuredReferenceString::<E, M>::new_srs_for_testing(&mut rng).unwrap();
     let prover_param: ProverParam<E, M> = (&srs).into();
     let verifier_param: VerifierParam<E, M> = (&srs).into();
     let message: Vec<<E as PairingEngine>::Fr> = (0..M)
@@ -251,52 +266,49 @@ where
         .collect();
 
     let (comm, rand) = KZG10::<E, P>::commit(&ck, &p, None, None).unwrap();
-    
 
-    let commitment = Commitment::<E, M>::commit(&prover_param, &message);
+    let commitment = Commitment::<E, M>::commit(&prover_param, &message).unwrap();
     let prover_param_clone = prover_param.clone();
     let message_clone = message.clone();
     let bench_str = "pps_commit";
     ttt.bench_function(bench_str, move |b| {
         b.iter(|| {
-            let _ = Commitment::<E, M>::commit(&prover_param_clone, &message_clone);
+            let _ = Commitment::<E, M>::commit(&prover_param_clone, &message_clone).unwrap();
         });
     });
 
-    
     // =================
     // open
     // =================
-    let bench_str = format!("kzg_prove");
+    let bench_str = "kzg_prove";
     let p_clone = p.clone();
     let ck_clone = ck.clone();
     let rand_clone = rand.clone();
     ttt.bench_function(bench_str, move |b| {
         b.iter(|| {
-            let _ = open(&ck_clone, &p_clone, point, &rand_clone);
+            let _ = open(&ck_clone, &p_clone, point, &rand_clone).unwrap();
         })
     });
 
     let value = p.evaluate(&point);
-    let proof = open(&ck, &p, point, &rand);
-
+    let proof = open(&ck, &p, point, &rand).unwrap();
 
     let pos = (rng.next_u32() % dim as u32) as usize;
     let m = message[pos];
-    let witness = Commitment::<E, M>::open(&prover_param, &message, pos);
+    let witness = Commitment::<E, M>::open(&prover_param, &message, pos).unwrap();
 
     let bench_str = "pps_prove";
     ttt.bench_function(bench_str, move |b| {
         b.iter(|| {
             let pos = (rng.next_u32() % dim as u32) as usize;
-            let _ = Commitment::<E, M>::open(&prover_param, &message, pos);
+            let _ = Commitment::<E, M>::open(&prover_param, &message, pos).unwrap();
         });
     });
 
     // =================
     // verify
     // =================
-    let bench_str = format!("kzg_verify");
+    let bench_str = "kzg_verify";
     ttt.bench_function(bench_str, move |b| {
         b.iter(|| {
             assert!(
@@ -310,7 +322,10 @@ where
 
     let bench_str = "pps_verify";
     ttt.bench_function(bench_str, move |b| {
-        b.iter(|| assert!(commitment.verify(&verifier_param, &m, pos, &witness)));
+        b.iter(|| {
+            assert!(commitment
+                .verify(&verifier_param, &m, pos, &witness)
+                .unwrap())
+        });
     });
-
 }
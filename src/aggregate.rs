@@ -0,0 +1,448 @@
+use crate::error::check_dimension;
+use crate::error::check_input_length;
+use crate::error::check_position;
+use crate::error::check_same_length;
+use crate::param::DynProverParam;
+use crate::param::DynVerifierParam;
+use crate::param::ProverParam;
+use crate::param::VerifierParam;
+use crate::transcript::Transcript;
+use crate::Commitment;
+use crate::DynCommitment;
+use crate::PointproofError;
+use ark_ec::msm::VariableBaseMSM;
+use ark_ec::AffineCurve;
+use ark_ec::PairingEngine;
+use ark_ec::ProjectiveCurve;
+use ark_ff::Field;
+use ark_ff::PrimeField;
+use ark_ff::Zero;
+use std::ops::Neg;
+
+/// Domain separator for the coefficients of a same-commitment aggregation
+const SAME_COMMITMENT_DOMAIN: &[u8] = b"pointproofs same commitment aggregation";
+
+/// Domain separator for the coefficients of a cross-commitment aggregation
+const CROSS_COMMITMENT_DOMAIN: &[u8] = b"pointproofs cross commitment aggregation";
+
+impl<E: PairingEngine> DynCommitment<E> {
+    /// Aggregate the witnesses of the inputs at positions `set` into a single proof
+    pub fn aggregate_proofs(
+        &self,
+        set: &[usize],
+        inputs: &[E::Fr],
+        witnesses: &[E::G1Projective],
+    ) -> Result<E::G1Projective, PointproofError> {
+        check_same_length(set.len(), inputs.len())?;
+        check_same_length(set.len(), witnesses.len())?;
+
+        let coeffs = self.aggregation_coefficients(set, inputs);
+        let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+            coeffs.iter().map(|x| x.into_repr()).collect();
+        let bases = E::G1Projective::batch_normalization_into_affine(witnesses);
+        Ok(VariableBaseMSM::multi_scalar_mul(&bases, &scalars))
+    }
+
+    /// Open the inputs at positions `set` with a single aggregated proof,
+    /// where `self` is the commitment to `inputs`
+    pub fn aggregate_open(
+        &self,
+        pp: &DynProverParam<E>,
+        inputs: &[E::Fr],
+        set: &[usize],
+    ) -> Result<E::G1Projective, PointproofError> {
+        let n = self.n;
+        check_dimension(n, pp.dimension())?;
+        check_input_length(inputs.len(), n)?;
+        for &pos in set.iter() {
+            check_position(pos, n)?;
+        }
+
+        let values: Vec<E::Fr> = set
+            .iter()
+            .map(|&pos| inputs.get(pos).copied().unwrap_or_else(E::Fr::zero))
+            .collect();
+        let coeffs = self.aggregation_coefficients(set, &values);
+
+        // the aggregated proof is \sum_i t_i \sum_j m_j * g[n - i + j],
+        // which we collect into a single MSM over g
+        let mut scalars = vec![E::Fr::zero(); n << 1];
+        for (&pos, t) in set.iter().zip(coeffs.iter()) {
+            for (j, m) in inputs.iter().enumerate() {
+                scalars[n - pos + j] += *t * m;
+            }
+        }
+        let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+            scalars.iter().map(|x| x.into_repr()).collect();
+        Ok(VariableBaseMSM::multi_scalar_mul(&pp.g, &scalars))
+    }
+
+    /// Verify an aggregated proof for the inputs at positions `set`
+    pub fn aggregate_verify(
+        &self,
+        vp: &DynVerifierParam<E>,
+        set: &[usize],
+        inputs: &[E::Fr],
+        proof: &E::G1Projective,
+    ) -> Result<bool, PointproofError> {
+        let n = self.n;
+        check_dimension(n, vp.dimension())?;
+        check_same_length(set.len(), inputs.len())?;
+        for &pos in set.iter() {
+            check_position(pos, n)?;
+        }
+
+        let coeffs = self.aggregation_coefficients(set, inputs);
+        let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+            coeffs.iter().map(|x| x.into_repr()).collect();
+        let bases: Vec<E::G2Affine> = set.iter().map(|&pos| vp.h[n - pos - 1]).collect();
+        let h = VariableBaseMSM::multi_scalar_mul(&bases, &scalars);
+        let exponent: E::Fr = inputs.iter().zip(coeffs.iter()).map(|(m, t)| *m * t).sum();
+
+        // e(C, \sum_i t_i * h[n - i - 1]) * e(-proof, H) = t^{\sum_i t_i m_i}
+        let pairing_prod_inputs = [
+            (self.commitment.into_affine().into(), h.into_affine().into()),
+            (
+                proof.neg().into_affine().into(),
+                E::G2Affine::prime_subgroup_generator().into(),
+            ),
+        ];
+        Ok(E::product_of_pairings(pairing_prod_inputs.iter()) == vp.t.pow(exponent.into_repr()))
+    }
+
+    /// Derive the aggregation coefficients t_i = H(C, S, m\[S\], i)
+    pub(crate) fn aggregation_coefficients(&self, set: &[usize], inputs: &[E::Fr]) -> Vec<E::Fr> {
+        let mut transcript = Transcript::new(SAME_COMMITMENT_DOMAIN);
+        self.append_claim(&mut transcript, set, inputs);
+        transcript.challenge_scalars(b"coefficient", set.len())
+    }
+
+    /// Absorb the commitment together with the claimed positions and inputs
+    fn append_claim(&self, transcript: &mut Transcript<E>, set: &[usize], inputs: &[E::Fr]) {
+        transcript.append_commitment(b"commitment", self);
+        transcript.append_positions(b"positions", set);
+        transcript.append_scalars(b"inputs", inputs);
+    }
+
+    /// Aggregate same-commitment proofs of several commitments into a single proof.
+    /// Each proof is the output of `aggregate_proofs` or `aggregate_open` for the
+    /// matching commitment, positions and inputs.
+    pub fn cross_commitment_aggregate(
+        commitments: &[Self],
+        sets: &[&[usize]],
+        inputs: &[&[E::Fr]],
+        proofs: &[E::G1Projective],
+    ) -> Result<E::G1Projective, PointproofError> {
+        check_same_length(commitments.len(), sets.len())?;
+        check_same_length(commitments.len(), inputs.len())?;
+        check_same_length(commitments.len(), proofs.len())?;
+        for (set, input) in sets.iter().zip(inputs.iter()) {
+            check_same_length(set.len(), input.len())?;
+        }
+
+        let coeffs = Self::cross_commitment_coefficients(commitments, sets, inputs);
+        let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+            coeffs.iter().map(|x| x.into_repr()).collect();
+        let bases = E::G1Projective::batch_normalization_into_affine(proofs);
+        Ok(VariableBaseMSM::multi_scalar_mul(&bases, &scalars))
+    }
+
+    /// Verify a cross-commitment proof with a single product of l+1 pairings
+    pub fn cross_commitment_verify(
+        vp: &DynVerifierParam<E>,
+        commitments: &[Self],
+        sets: &[&[usize]],
+        inputs: &[&[E::Fr]],
+        proof: &E::G1Projective,
+    ) -> Result<bool, PointproofError> {
+        let n = vp.dimension();
+        check_same_length(commitments.len(), sets.len())?;
+        check_same_length(commitments.len(), inputs.len())?;
+        for ((com, set), input) in commitments.iter().zip(sets.iter()).zip(inputs.iter()) {
+            check_dimension(n, com.n)?;
+            check_same_length(set.len(), input.len())?;
+            for &pos in set.iter() {
+                check_position(pos, n)?;
+            }
+        }
+
+        let coeffs = Self::cross_commitment_coefficients(commitments, sets, inputs);
+        let mut exponent = E::Fr::zero();
+        let mut pairing_prod_inputs = Vec::with_capacity(commitments.len() + 1);
+        for (((com, set), input), t_prime) in commitments
+            .iter()
+            .zip(sets.iter())
+            .zip(inputs.iter())
+            .zip(coeffs.iter())
+        {
+            let inner = com.aggregation_coefficients(set, input);
+            let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+                inner.iter().map(|x| x.into_repr()).collect();
+            let bases: Vec<E::G2Affine> = set.iter().map(|&pos| vp.h[n - pos - 1]).collect();
+            let h = VariableBaseMSM::multi_scalar_mul(&bases, &scalars);
+            let inner_exponent: E::Fr = input.iter().zip(inner.iter()).map(|(m, t)| *m * t).sum();
+            exponent += *t_prime * inner_exponent;
+
+            let c = com.commitment.mul(t_prime.into_repr());
+            pairing_prod_inputs.push((c.into_affine().into(), h.into_affine().into()));
+        }
+
+        // \prod_j e(t'_j * C_j, \sum_i t_{j,i} * h[n - i - 1]) * e(-proof, H)
+        //     = t^{\sum_j t'_j \sum_i t_{j,i} m_{j,i}}
+        pairing_prod_inputs.push((
+            proof.neg().into_affine().into(),
+            E::G2Affine::prime_subgroup_generator().into(),
+        ));
+        Ok(E::product_of_pairings(pairing_prod_inputs.iter()) == vp.t.pow(exponent.into_repr()))
+    }
+
+    /// Derive the cross-commitment coefficients t'_j = H({C_j, S_j, m\[S_j\]}, j)
+    fn cross_commitment_coefficients(
+        commitments: &[Self],
+        sets: &[&[usize]],
+        inputs: &[&[E::Fr]],
+    ) -> Vec<E::Fr> {
+        let mut transcript = Transcript::new(CROSS_COMMITMENT_DOMAIN);
+        transcript.append_position(b"number of commitments", commitments.len());
+        for ((com, set), input) in commitments.iter().zip(sets.iter()).zip(inputs.iter()) {
+            com.append_claim(&mut transcript, set, input);
+        }
+        transcript.challenge_scalars(b"coefficient", commitments.len())
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Commitment<E, N> {
+    /// Aggregate the witnesses of the inputs at positions `set` into a single proof
+    pub fn aggregate_proofs(
+        &self,
+        set: &[usize],
+        inputs: &[E::Fr],
+        witnesses: &[E::G1Projective],
+    ) -> Result<E::G1Projective, PointproofError> {
+        self.inner.aggregate_proofs(set, inputs, witnesses)
+    }
+
+    /// Open the inputs at positions `set` with a single aggregated proof,
+    /// where `self` is the commitment to `inputs`
+    pub fn aggregate_open(
+        &self,
+        pp: &ProverParam<E, N>,
+        inputs: &[E::Fr],
+        set: &[usize],
+    ) -> Result<E::G1Projective, PointproofError> {
+        self.inner.aggregate_open(&pp.inner, inputs, set)
+    }
+
+    /// Verify an aggregated proof for the inputs at positions `set`
+    pub fn aggregate_verify(
+        &self,
+        vp: &VerifierParam<E, N>,
+        set: &[usize],
+        inputs: &[E::Fr],
+        proof: &E::G1Projective,
+    ) -> Result<bool, PointproofError> {
+        self.inner.aggregate_verify(&vp.inner, set, inputs, proof)
+    }
+
+    /// Aggregate same-commitment proofs of several commitments into a single proof.
+    /// Each proof is the output of `aggregate_proofs` or `aggregate_open` for the
+    /// matching commitment, positions and inputs.
+    pub fn cross_commitment_aggregate(
+        commitments: &[Self],
+        sets: &[&[usize]],
+        inputs: &[&[E::Fr]],
+        proofs: &[E::G1Projective],
+    ) -> Result<E::G1Projective, PointproofError> {
+        let commitments: Vec<DynCommitment<E>> = commitments.iter().map(|c| c.inner).collect();
+        DynCommitment::cross_commitment_aggregate(&commitments, sets, inputs, proofs)
+    }
+
+    /// Verify a cross-commitment proof with a single product of l+1 pairings
+    pub fn cross_commitment_verify(
+        vp: &VerifierParam<E, N>,
+        commitments: &[Self],
+        sets: &[&[usize]],
+        inputs: &[&[E::Fr]],
+        proof: &E::G1Projective,
+    ) -> Result<bool, PointproofError> {
+        let commitments: Vec<DynCommitment<E>> = commitments.iter().map(|c| c.inner).collect();
+        DynCommitment::cross_commitment_verify(&vp.inner, &commitments, sets, inputs, proof)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CommitmentScheme;
+    use crate::StructuredReferenceString;
+    use ark_bn254::Bn254;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    const NUM_TEST: usize = 10;
+
+    macro_rules! test_same_commitment_aggregation {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            let message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let commitment = Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap();
+
+            let set: Vec<usize> = (0..NUM_TEST)
+                .map(|_| (rng.next_u32() % $dim) as usize)
+                .collect();
+            let values: Vec<<$engine as PairingEngine>::Fr> =
+                set.iter().map(|&pos| message[pos]).collect();
+            let witnesses: Vec<<$engine as PairingEngine>::G1Projective> = set
+                .iter()
+                .map(|&pos| {
+                    Commitment::<$engine, $dim>::open(&prover_param, &message, pos).unwrap()
+                })
+                .collect();
+
+            let proof = commitment
+                .aggregate_proofs(&set, &values, &witnesses)
+                .unwrap();
+            assert!(commitment
+                .aggregate_verify(&verifier_param, &set, &values, &proof)
+                .unwrap());
+            assert_eq!(
+                proof,
+                commitment
+                    .aggregate_open(&prover_param, &message, &set)
+                    .unwrap()
+            );
+
+            let mut wrong_values = values.clone();
+            wrong_values[0] += <$engine as PairingEngine>::Fr::from(1u64);
+            assert!(!commitment
+                .aggregate_verify(&verifier_param, &set, &wrong_values, &proof)
+                .unwrap());
+
+            // positions beyond the message length open to zero
+            let short_message = &message[..$dim / 2];
+            let commitment =
+                Commitment::<$engine, $dim>::commit(&prover_param, short_message).unwrap();
+            let set = [0, $dim / 2, $dim - 1];
+            let values = [
+                message[0],
+                <$engine as PairingEngine>::Fr::zero(),
+                <$engine as PairingEngine>::Fr::zero(),
+            ];
+            let proof = commitment
+                .aggregate_open(&prover_param, short_message, &set)
+                .unwrap();
+            assert!(commitment
+                .aggregate_verify(&verifier_param, &set, &values, &proof)
+                .unwrap());
+
+            // malformed inputs are rejected
+            assert!(matches!(
+                commitment.aggregate_verify(&verifier_param, &[$dim], &values[..1], &proof),
+                Err(PointproofError::PositionOutOfRange { .. })
+            ));
+            assert!(matches!(
+                commitment.aggregate_verify(&verifier_param, &set, &values[..1], &proof),
+                Err(PointproofError::LengthMismatch { .. })
+            ));
+        };
+    }
+
+    macro_rules! test_cross_commitment_aggregation {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            let mut commitments = Vec::new();
+            let mut sets = Vec::new();
+            let mut values = Vec::new();
+            let mut proofs = Vec::new();
+            for i in 0..NUM_TEST {
+                let message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                    .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                    .collect();
+                let commitment =
+                    Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap();
+                let set: Vec<usize> = (0..i + 1)
+                    .map(|_| (rng.next_u32() % $dim) as usize)
+                    .collect();
+                let value: Vec<<$engine as PairingEngine>::Fr> =
+                    set.iter().map(|&pos| message[pos]).collect();
+                proofs.push(
+                    commitment
+                        .aggregate_open(&prover_param, &message, &set)
+                        .unwrap(),
+                );
+                commitments.push(commitment);
+                sets.push(set);
+                values.push(value);
+            }
+            let sets: Vec<&[usize]> = sets.iter().map(|x| x.as_ref()).collect();
+            let values: Vec<&[<$engine as PairingEngine>::Fr]> =
+                values.iter().map(|x| x.as_ref()).collect();
+
+            let proof = Commitment::<$engine, $dim>::cross_commitment_aggregate(
+                &commitments,
+                &sets,
+                &values,
+                &proofs,
+            )
+            .unwrap();
+            assert!(Commitment::<$engine, $dim>::cross_commitment_verify(
+                &verifier_param,
+                &commitments,
+                &sets,
+                &values,
+                &proof
+            )
+            .unwrap());
+
+            // swapping the order of two commitments must not verify
+            let mut swapped = commitments.clone();
+            swapped.swap(0, 1);
+            assert!(!Commitment::<$engine, $dim>::cross_commitment_verify(
+                &verifier_param,
+                &swapped,
+                &sets,
+                &values,
+                &proof
+            )
+            .unwrap());
+
+            let mut wrong_value = values[1].to_vec();
+            wrong_value[0] += <$engine as PairingEngine>::Fr::from(1u64);
+            let mut wrong_values = values.clone();
+            wrong_values[1] = &wrong_value;
+            assert!(!Commitment::<$engine, $dim>::cross_commitment_verify(
+                &verifier_param,
+                &commitments,
+                &sets,
+                &wrong_values,
+                &proof
+            )
+            .unwrap());
+        };
+    }
+
+    #[test]
+    fn test_same_commitment_aggregation() {
+        test_same_commitment_aggregation!(Bn254, 128, "bn254");
+    }
+
+    #[test]
+    fn test_cross_commitment_aggregation() {
+        test_cross_commitment_aggregation!(Bn254, 128, "bn254");
+    }
+}
@@ -0,0 +1,168 @@
+use crate::error::check_dimension;
+use crate::error::check_position;
+use crate::param::DynVerifierParam;
+use crate::param::VerifierParam;
+use crate::transcript::Transcript;
+use crate::Commitment;
+use crate::DynCommitment;
+use crate::PointproofError;
+use ark_ec::msm::VariableBaseMSM;
+use ark_ec::AffineCurve;
+use ark_ec::PairingEngine;
+use ark_ec::ProjectiveCurve;
+use ark_ff::Field;
+use ark_ff::PrimeField;
+use std::collections::BTreeMap;
+use std::ops::Neg;
+
+/// Domain separator for the coefficients of a batch verification
+const BATCH_DOMAIN: &[u8] = b"pointproofs batch verification";
+
+impl<E: PairingEngine> DynCommitment<E> {
+    /// Verify a list of (commitment, position, input, witness) openings at once.
+    /// The openings are combined with coefficients derived from a transcript of
+    /// all of them, and openings that share a position share a single pairing.
+    pub fn batch_verify(
+        vp: &DynVerifierParam<E>,
+        openings: &[(Self, usize, E::Fr, E::G1Projective)],
+    ) -> Result<bool, PointproofError> {
+        let n = vp.dimension();
+        for (com, pos, _, _) in openings.iter() {
+            check_dimension(n, com.n)?;
+            check_position(*pos, n)?;
+        }
+
+        let coeffs = Self::batch_coefficients(openings);
+        let exponent: E::Fr = openings
+            .iter()
+            .zip(coeffs.iter())
+            .map(|((_, _, input, _), r)| *input * r)
+            .sum();
+
+        // group the openings by position, so that each h[n - pos - 1]
+        // is paired only once
+        let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (i, (_, pos, _, _)) in openings.iter().enumerate() {
+            groups.entry(*pos).or_default().push(i);
+        }
+
+        let mut pairing_prod_inputs = Vec::with_capacity(groups.len() + 1);
+        for (pos, indices) in groups.iter() {
+            let commitments: Vec<E::G1Projective> =
+                indices.iter().map(|&i| openings[i].0.commitment).collect();
+            let bases = E::G1Projective::batch_normalization_into_affine(&commitments);
+            let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+                indices.iter().map(|&i| coeffs[i].into_repr()).collect();
+            let c = VariableBaseMSM::multi_scalar_mul(&bases, &scalars);
+            pairing_prod_inputs.push((c.into_affine().into(), vp.h[n - pos - 1].into()));
+        }
+
+        let witnesses: Vec<E::G1Projective> = openings.iter().map(|(_, _, _, w)| *w).collect();
+        let bases = E::G1Projective::batch_normalization_into_affine(&witnesses);
+        let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+            coeffs.iter().map(|x| x.into_repr()).collect();
+        let w = VariableBaseMSM::multi_scalar_mul(&bases, &scalars);
+        // \prod_pos e(\sum_k r_k * C_k, h[n - pos - 1]) * e(-\sum_k r_k * w_k, H)
+        //     = t^{\sum_k r_k m_k}
+        pairing_prod_inputs.push((
+            w.neg().into_affine().into(),
+            E::G2Affine::prime_subgroup_generator().into(),
+        ));
+        Ok(E::product_of_pairings(pairing_prod_inputs.iter()) == vp.t.pow(exponent.into_repr()))
+    }
+
+    /// Derive the batching coefficients r_k = H({C_k, pos_k, m_k, w_k}, k)
+    fn batch_coefficients(openings: &[(Self, usize, E::Fr, E::G1Projective)]) -> Vec<E::Fr> {
+        let mut transcript = Transcript::new(BATCH_DOMAIN);
+        transcript.append_position(b"number of openings", openings.len());
+        for (com, pos, input, witness) in openings.iter() {
+            transcript.append_commitment(b"commitment", com);
+            transcript.append_position(b"position", *pos);
+            transcript.append_scalar(b"input", input);
+            transcript.append_g1(b"witness", &witness.into_affine());
+        }
+        transcript.challenge_scalars(b"coefficient", openings.len())
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Commitment<E, N> {
+    /// Verify a list of (commitment, position, input, witness) openings at once.
+    /// The openings are combined with coefficients derived from a transcript of
+    /// all of them, and openings that share a position share a single pairing.
+    pub fn batch_verify(
+        vp: &VerifierParam<E, N>,
+        openings: &[(Self, usize, E::Fr, E::G1Projective)],
+    ) -> Result<bool, PointproofError> {
+        let openings: Vec<_> = openings
+            .iter()
+            .map(|&(com, pos, input, witness)| (com.inner, pos, input, witness))
+            .collect();
+        DynCommitment::batch_verify(&vp.inner, &openings)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::param::ProverParam;
+    use crate::CommitmentScheme;
+    use crate::StructuredReferenceString;
+    use ark_bn254::Bn254;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    const NUM_TEST: usize = 10;
+
+    macro_rules! test_batch_verify {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            let mut openings = Vec::new();
+            for _ in 0..3 {
+                let message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                    .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                    .collect();
+                let commitment =
+                    Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap();
+                for _ in 0..NUM_TEST {
+                    // a small range of positions, so that some of them repeat
+                    let pos = (rng.next_u32() % 8) as usize;
+                    let witness =
+                        Commitment::<$engine, $dim>::open(&prover_param, &message, pos).unwrap();
+                    openings.push((commitment, pos, message[pos], witness));
+                }
+            }
+            assert!(Commitment::<$engine, $dim>::batch_verify(&verifier_param, &openings).unwrap());
+            assert!(Commitment::<$engine, $dim>::batch_verify(&verifier_param, &[]).unwrap());
+
+            let mut out_of_range = openings.clone();
+            out_of_range[0].1 = $dim;
+            assert!(matches!(
+                Commitment::<$engine, $dim>::batch_verify(&verifier_param, &out_of_range),
+                Err(PointproofError::PositionOutOfRange { .. })
+            ));
+
+            let mut bad_witness = openings.clone();
+            bad_witness[0].3.double_in_place();
+            assert!(
+                !Commitment::<$engine, $dim>::batch_verify(&verifier_param, &bad_witness).unwrap()
+            );
+
+            openings[NUM_TEST].2 += <$engine as PairingEngine>::Fr::from(1u64);
+            assert!(
+                !Commitment::<$engine, $dim>::batch_verify(&verifier_param, &openings).unwrap()
+            );
+        };
+    }
+
+    #[test]
+    fn test_batch_verify() {
+        test_batch_verify!(Bn254, 128, "bn254");
+    }
+}
@@ -0,0 +1,160 @@
+use crate::error::check_dimension;
+use crate::error::check_input_length;
+use crate::param::DynProverParam;
+use crate::param::DynVerifierParam;
+use crate::param::ProverParam;
+use crate::param::VerifierParam;
+use crate::transcript::Transcript;
+use crate::Commitment;
+use crate::DynCommitment;
+use crate::PointproofError;
+use ark_ec::PairingEngine;
+use ark_std::Zero;
+
+/// Domain separator for the coefficients of a length bound proof
+const LENGTH_BOUND_DOMAIN: &[u8] = b"pointproofs length bound";
+
+// The vector has length at most k iff m_i = 0 for i >= k. With coefficients
+// t_i = H(C, k, i) for i in k..n, a length bound proof is the functional opening
+// of \sum_{i >= k} t_i m_i = 0, i.e., \sum_{i >= k} t_i w_i, which is checked with
+//   e(C, \sum_{i >= k} t_i h[n - i - 1]) = e(proof, H).
+// If some m_i with i >= k is non-zero, the sum is zero only with negligible
+// probability over the coefficients, which are fixed once C and k are.
+impl<E: PairingEngine> DynCommitment<E> {
+    /// Prove that only the first `k` inputs may be non-zero, where `self` is the
+    /// commitment to `inputs`
+    pub fn length_bound_open(
+        &self,
+        pp: &DynProverParam<E>,
+        inputs: &[E::Fr],
+        k: usize,
+    ) -> Result<E::G1Projective, PointproofError> {
+        let n = self.n;
+        check_dimension(n, pp.dimension())?;
+        check_input_length(k, n)?;
+        if let Some(pos) = inputs.iter().rposition(|m| !m.is_zero()) {
+            check_input_length(pos + 1, k)?;
+        }
+
+        Self::functional_open(pp, inputs, &self.length_bound_weights(k))
+    }
+
+    /// Verify that only the first `k` committed inputs may be non-zero
+    pub fn length_bound_verify(
+        &self,
+        vp: &DynVerifierParam<E>,
+        k: usize,
+        proof: &E::G1Projective,
+    ) -> Result<bool, PointproofError> {
+        let n = self.n;
+        check_dimension(n, vp.dimension())?;
+        check_input_length(k, n)?;
+
+        self.functional_verify(vp, &self.length_bound_weights(k), &E::Fr::zero(), proof)
+    }
+
+    /// The weights 0 for the positions below k, and t_i = H(C, k, i) from k on
+    fn length_bound_weights(&self, k: usize) -> Vec<E::Fr> {
+        let mut transcript = Transcript::new(LENGTH_BOUND_DOMAIN);
+        transcript.append_commitment(b"commitment", self);
+        transcript.append_position(b"bound", k);
+
+        let mut weights = vec![E::Fr::zero(); k];
+        weights.extend(transcript.challenge_scalars(b"coefficient", self.n - k));
+        weights
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Commitment<E, N> {
+    /// Prove that only the first `k` inputs may be non-zero, where `self` is the
+    /// commitment to `inputs`
+    pub fn length_bound_open(
+        &self,
+        pp: &ProverParam<E, N>,
+        inputs: &[E::Fr],
+        k: usize,
+    ) -> Result<E::G1Projective, PointproofError> {
+        self.inner.length_bound_open(&pp.inner, inputs, k)
+    }
+
+    /// Verify that only the first `k` committed inputs may be non-zero
+    pub fn length_bound_verify(
+        &self,
+        vp: &VerifierParam<E, N>,
+        k: usize,
+        proof: &E::G1Projective,
+    ) -> Result<bool, PointproofError> {
+        self.inner.length_bound_verify(&vp.inner, k, proof)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CommitmentScheme;
+    use crate::StructuredReferenceString;
+    use ark_bn254::Bn254;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    macro_rules! test_length_bound {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            let k = $dim / 4;
+            let message: Vec<<$engine as PairingEngine>::Fr> = (0..k)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let commitment = Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap();
+            for bound in [k, k + 1, $dim] {
+                let proof = commitment
+                    .length_bound_open(&prover_param, &message, bound)
+                    .unwrap();
+                assert!(commitment
+                    .length_bound_verify(&verifier_param, bound, &proof)
+                    .unwrap());
+            }
+            // a proof is bound to k
+            let proof = commitment
+                .length_bound_open(&prover_param, &message, k)
+                .unwrap();
+            assert!(!commitment
+                .length_bound_verify(&verifier_param, k + 1, &proof)
+                .unwrap());
+
+            // a non-zero input at position k
+            let mut long_message = message.clone();
+            long_message.push(<$engine as PairingEngine>::Fr::rand(&mut rng));
+            let long_commitment =
+                Commitment::<$engine, $dim>::commit(&prover_param, &long_message).unwrap();
+            assert!(matches!(
+                long_commitment.length_bound_open(&prover_param, &long_message, k),
+                Err(PointproofError::InputTooLong { .. })
+            ));
+            let proof = Commitment::<$engine, $dim>::functional_open(
+                &prover_param,
+                &long_message,
+                &long_commitment.inner.length_bound_weights(k),
+            )
+            .unwrap();
+            assert!(!long_commitment
+                .length_bound_verify(&verifier_param, k, &proof)
+                .unwrap());
+
+            assert!(matches!(
+                commitment.length_bound_verify(&verifier_param, $dim + 1, &proof),
+                Err(PointproofError::InputTooLong { .. })
+            ));
+        };
+    }
+
+    #[test]
+    fn test_length_bound() {
+        test_length_bound!(Bn254, 64, "bn254");
+    }
+}
@@ -0,0 +1,329 @@
+use crate::error::malformed;
+use crate::param::check_dyn_parameters;
+use crate::param::DynProverParam;
+use crate::param::DynStructuredReferenceString;
+use crate::param::DynVerifierParam;
+use crate::param::StructuredReferenceString;
+use crate::PointproofError;
+use ark_ec::AffineCurve;
+use ark_ec::PairingEngine;
+use ark_ec::ProjectiveCurve;
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalDeserialize;
+use ark_serialize::CanonicalSerialize;
+use ark_serialize::Read;
+use ark_serialize::SerializationError;
+use ark_serialize::Write;
+use ark_std::rand::Rng;
+use ark_std::One;
+use ark_std::UniformRand;
+use ark_std::Zero;
+#[cfg(feature = "parallel")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "parallel")]
+use rayon::iter::IntoParallelRefIterator;
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+use sha2::Digest;
+use sha2::Sha512;
+use std::ops::Neg;
+use zeroize::Zeroize;
+
+/// Domain separator for the challenge of the proof of knowledge of a contribution
+const CONTRIBUTION_DOMAIN: &[u8] = b"pointproofs ceremony contribution";
+
+/// A participant's update of the SRS from alpha to alpha * beta, together with
+/// a proof that the update is well-formed:
+/// - alpha_g = alpha * beta * G, the first G1 power after the update
+/// - beta_g = beta * G and beta_h = beta * H
+/// - a Schnorr proof of knowledge of beta, bound to the SRS before the update
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Contribution<E: PairingEngine> {
+    pub(crate) alpha_g: E::G1Affine,
+    pub(crate) beta_g: E::G1Affine,
+    pub(crate) beta_h: E::G2Affine,
+    pub(crate) pok_commitment: E::G1Affine,
+    pub(crate) pok_response: E::Fr,
+}
+
+impl<E: PairingEngine> DynStructuredReferenceString<E> {
+    /// The starting point of a ceremony of dimension `n`, i.e., the SRS for alpha = 1.
+    /// It is only secure once at least one honest participant contributed.
+    pub fn ceremony_start(n: usize) -> Result<Self, PointproofError> {
+        if n == 0 {
+            return Err(malformed("the dimension must be positive"));
+        }
+
+        let g1 = E::G1Affine::prime_subgroup_generator();
+        let g2 = E::G2Affine::prime_subgroup_generator();
+        let mut g = vec![g1; n << 1];
+        g[n] = E::G1Affine::zero();
+
+        Ok(Self {
+            g0: g1,
+            g,
+            h: vec![g2; n],
+            t: E::pairing(g1, g2),
+        })
+    }
+
+    /// Update the SRS with a secret beta sampled from `rng`, and return the
+    /// proof of the update. The slot for alpha^{n+1} stays empty, and t is
+    /// recomputed as e(alpha^n * G, alpha * H). Beta, its powers and the nonce
+    /// of the proof are zeroized before returning.
+    pub fn contribute<R: Rng>(&mut self, rng: &mut R) -> Result<Contribution<E>, PointproofError> {
+        let n = self.dimension();
+        if n == 0 || self.g.len() != n << 1 {
+            return Err(malformed("wrong number of powers"));
+        }
+
+        let mut beta = E::Fr::rand(rng);
+        while beta.is_zero() {
+            beta = E::Fr::rand(rng);
+        }
+        let mut beta_powers = Vec::<E::Fr>::with_capacity(n << 1);
+        beta_powers.push(beta);
+        for _ in 1..n << 1 {
+            beta_powers.push(beta * beta_powers.last().unwrap())
+        }
+
+        let prev_alpha_g = self.g[0];
+        let g: Vec<E::G1Projective> = ark_std::cfg_iter!(self.g)
+            .zip(ark_std::cfg_iter!(beta_powers))
+            .map(|(g, beta_power)| g.mul(*beta_power))
+            .collect();
+        let h: Vec<E::G2Projective> = ark_std::cfg_iter!(self.h)
+            .zip(ark_std::cfg_iter!(beta_powers))
+            .map(|(h, beta_power)| h.mul(*beta_power))
+            .collect();
+        self.g = E::G1Projective::batch_normalization_into_affine(&g);
+        self.h = E::G2Projective::batch_normalization_into_affine(&h);
+        self.t = E::pairing(self.g[n - 1], self.h[0]);
+
+        let beta_g = E::G1Affine::prime_subgroup_generator()
+            .mul(beta)
+            .into_affine();
+        let beta_h = E::G2Affine::prime_subgroup_generator()
+            .mul(beta)
+            .into_affine();
+        let mut k = E::Fr::rand(rng);
+        let pok_commitment = E::G1Affine::prime_subgroup_generator().mul(k).into_affine();
+        let c = contribution_challenge::<E>(&prev_alpha_g, &beta_g, &beta_h, &pok_commitment);
+        let pok_response = k + c * beta;
+
+        // beta, its powers and the nonce are the toxic waste of the contribution
+        beta.zeroize();
+        beta_powers.zeroize();
+        k.zeroize();
+
+        Ok(Contribution {
+            alpha_g: self.g[0],
+            beta_g,
+            beta_h,
+            pok_commitment,
+            pok_response,
+        })
+    }
+
+    /// Verify that `self` is the result of the contributions, in order, starting
+    /// from `ceremony_start`, and that `self` is a valid SRS.
+    pub fn verify_ceremony<R: Rng>(
+        &self,
+        contributions: &[Contribution<E>],
+        rng: &mut R,
+    ) -> Result<(), PointproofError> {
+        self.verify_contributions(E::G1Affine::prime_subgroup_generator(), contributions, rng)
+    }
+
+    /// Verify that `self` is the result of the contributions, in order, starting
+    /// from an SRS whose first G1 power is `start`, and that `self` is a valid SRS.
+    pub(crate) fn verify_contributions<R: Rng>(
+        &self,
+        start: E::G1Affine,
+        contributions: &[Contribution<E>],
+        rng: &mut R,
+    ) -> Result<(), PointproofError> {
+        if contributions.is_empty() {
+            return Err(malformed("the transcript is empty"));
+        }
+
+        let g1 = E::G1Affine::prime_subgroup_generator();
+        let g2 = E::G2Affine::prime_subgroup_generator();
+        let mut prev_alpha_g = start;
+        for contribution in contributions.iter() {
+            if contribution.beta_g.is_zero() {
+                return Err(malformed("a contribution is zero"));
+            }
+
+            // the participant knows beta
+            let c = contribution_challenge::<E>(
+                &prev_alpha_g,
+                &contribution.beta_g,
+                &contribution.beta_h,
+                &contribution.pok_commitment,
+            );
+            if g1.mul(contribution.pok_response)
+                != contribution.pok_commitment.into_projective() + contribution.beta_g.mul(c)
+            {
+                return Err(malformed("invalid proof of knowledge"));
+            }
+
+            // beta_g and beta_h share the same beta, and alpha_g = beta * prev_alpha_g:
+            //   e(beta_g + r * alpha_g, H) = e(G + r * prev_alpha_g, beta_h)
+            let r = E::Fr::rand(rng);
+            let lhs = contribution.beta_g.into_projective() + contribution.alpha_g.mul(r);
+            let rhs = g1.into_projective() + prev_alpha_g.mul(r);
+            let pairing_prod_inputs = [
+                (lhs.into_affine().into(), g2.into()),
+                (rhs.neg().into_affine().into(), contribution.beta_h.into()),
+            ];
+            if !E::product_of_pairings(pairing_prod_inputs.iter()).is_one() {
+                return Err(malformed("inconsistent contribution"));
+            }
+
+            prev_alpha_g = contribution.alpha_g;
+        }
+
+        if self.g0 != g1 {
+            return Err(malformed("the base is not the generator"));
+        }
+        if self.g.first() != Some(&prev_alpha_g) {
+            return Err(malformed("the SRS does not match the last contribution"));
+        }
+        let prover_param: DynProverParam<E> = self.into();
+        let verifier_param: DynVerifierParam<E> = self.into();
+        check_dyn_parameters(&prover_param, &verifier_param, rng)
+    }
+}
+
+impl<E: PairingEngine, const N: usize> StructuredReferenceString<E, N> {
+    /// The starting point of a ceremony, i.e., the SRS for alpha = 1.
+    /// It is only secure once at least one honest participant contributed.
+    pub fn ceremony_start() -> Result<Self, PointproofError> {
+        Ok(Self {
+            inner: DynStructuredReferenceString::ceremony_start(N)?,
+        })
+    }
+
+    /// Update the SRS with a secret beta sampled from `rng`, and return the
+    /// proof of the update
+    pub fn contribute<R: Rng>(&mut self, rng: &mut R) -> Result<Contribution<E>, PointproofError> {
+        self.inner.contribute(rng)
+    }
+
+    /// Verify that `self` is the result of the contributions, in order, starting
+    /// from `ceremony_start`, and that `self` is a valid SRS.
+    pub fn verify_ceremony<R: Rng>(
+        &self,
+        contributions: &[Contribution<E>],
+        rng: &mut R,
+    ) -> Result<(), PointproofError> {
+        self.inner.verify_ceremony(contributions, rng)
+    }
+}
+
+/// The challenge of the proof of knowledge,
+/// c = H(alpha * G before the update, beta * G, beta * H, R)
+fn contribution_challenge<E: PairingEngine>(
+    prev_alpha_g: &E::G1Affine,
+    beta_g: &E::G1Affine,
+    beta_h: &E::G2Affine,
+    pok_commitment: &E::G1Affine,
+) -> E::Fr {
+    let mut buf = Vec::new();
+    prev_alpha_g
+        .serialize(&mut buf)
+        .expect("serializing into a vector never fails");
+    beta_g
+        .serialize(&mut buf)
+        .expect("serializing into a vector never fails");
+    beta_h
+        .serialize(&mut buf)
+        .expect("serializing into a vector never fails");
+    pok_commitment
+        .serialize(&mut buf)
+        .expect("serializing into a vector never fails");
+    let digest = Sha512::new()
+        .chain_update(CONTRIBUTION_DOMAIN)
+        .chain_update(&buf)
+        .finalize();
+    E::Fr::from_le_bytes_mod_order(&digest)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::param::ProverParam;
+    use crate::param::VerifierParam;
+    use crate::Commitment;
+    use crate::CommitmentScheme;
+    use ark_bn254::Bn254;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+
+    const NUM_PARTICIPANTS: usize = 3;
+
+    macro_rules! test_ceremony {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let mut srs = StructuredReferenceString::<$engine, $dim>::ceremony_start().unwrap();
+            let mut contributions = Vec::new();
+            for _ in 0..NUM_PARTICIPANTS {
+                // each participant only sees the SRS of the previous one
+                let mut buf = Vec::new();
+                srs.serialize(&mut buf).unwrap();
+                let mut received =
+                    StructuredReferenceString::<$engine, $dim>::deserialize(&buf[..]).unwrap();
+                let contribution = received.contribute(&mut rng).unwrap();
+
+                let mut buf = Vec::new();
+                contribution.serialize(&mut buf).unwrap();
+                contributions.push(Contribution::<$engine>::deserialize(&buf[..]).unwrap());
+                srs = received;
+            }
+            srs.verify_ceremony(&contributions, &mut rng).unwrap();
+
+            // the resulting SRS works for commit, open and verify
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+            let message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let commitment = Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap();
+            let pos = (rng.next_u32() % $dim) as usize;
+            let witness = Commitment::<$engine, $dim>::open(&prover_param, &message, pos).unwrap();
+            assert!(commitment
+                .verify(&verifier_param, &message[pos], pos, &witness)
+                .unwrap());
+
+            // an empty or incomplete transcript is rejected
+            assert!(srs.verify_ceremony(&[], &mut rng).is_err());
+            assert!(srs.verify_ceremony(&contributions[1..], &mut rng).is_err());
+            assert!(srs
+                .verify_ceremony(&contributions[..NUM_PARTICIPANTS - 1], &mut rng)
+                .is_err());
+
+            // a proof of knowledge copied from another contribution is rejected
+            let mut bad_contributions = contributions.clone();
+            bad_contributions[1].pok_commitment = contributions[0].pok_commitment;
+            bad_contributions[1].pok_response = contributions[0].pok_response;
+            assert!(srs.verify_ceremony(&bad_contributions, &mut rng).is_err());
+
+            // an SRS modified after the last contribution is rejected
+            let mut bad_srs = srs.clone();
+            bad_srs.inner.g[3] = bad_srs.inner.g[4];
+            assert!(bad_srs.verify_ceremony(&contributions, &mut rng).is_err());
+
+            // so is an SRS whose base is not the generator
+            let mut bad_srs = srs.clone();
+            bad_srs.inner.g0 = bad_srs.inner.g[0];
+            assert!(bad_srs.verify_ceremony(&contributions, &mut rng).is_err());
+        };
+    }
+
+    #[test]
+    fn test_ceremony() {
+        test_ceremony!(Bn254, 128, "bn254");
+    }
+}
@@ -0,0 +1,230 @@
+use crate::error::check_input_length;
+use crate::error::check_position;
+use crate::param::DynProverParam;
+use crate::param::ProverParam;
+use crate::Commitment;
+use crate::DynCommitment;
+use crate::PointproofError;
+use ark_ec::msm::VariableBaseMSM;
+use ark_ec::PairingEngine;
+use ark_ff::PrimeField;
+
+// The MSMs of `commit` and `open` are linear in the inputs, so a vector split
+// into slices is committed to and opened slice by slice: the slice starting at
+// `offset` contributes \sum_j m_j * g[offset + j] to the commitment and
+// \sum_j m_j * g[n - pos + offset + j] to the witness of position pos, and the
+// contributions of all the slices add up to the centralized results.
+impl<E: PairingEngine> DynCommitment<E> {
+    /// Commit to the slice of the inputs that starts at position `offset`
+    pub fn partial_commit(
+        pp: &DynProverParam<E>,
+        offset: usize,
+        inputs: &[E::Fr],
+    ) -> Result<Self, PointproofError> {
+        let n = pp.dimension();
+        check_input_length(offset.saturating_add(inputs.len()), n)?;
+
+        let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+            inputs.iter().map(|x| x.into_repr()).collect();
+        Ok(Self {
+            n,
+            commitment: VariableBaseMSM::multi_scalar_mul(
+                &pp.g[offset..offset + inputs.len()],
+                &scalars,
+            ),
+        })
+    }
+
+    /// Open the slice of the inputs that starts at position `offset` at a given position
+    pub fn partial_open(
+        pp: &DynProverParam<E>,
+        offset: usize,
+        inputs: &[E::Fr],
+        pos: usize,
+    ) -> Result<E::G1Projective, PointproofError> {
+        let n = pp.dimension();
+        check_input_length(offset.saturating_add(inputs.len()), n)?;
+        check_position(pos, n)?;
+
+        let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+            inputs.iter().map(|x| x.into_repr()).collect();
+        let start = n - pos + offset;
+        Ok(VariableBaseMSM::multi_scalar_mul(
+            &pp.g[start..start + inputs.len()],
+            &scalars,
+        ))
+    }
+
+    /// Sum the partial commitments of the slices of a vector into its
+    /// commitment. There must be at least one partial commitment.
+    pub fn combine_partial_commitments(partials: &[Self]) -> Result<Self, PointproofError> {
+        let (first, rest) = partials.split_first().ok_or(PointproofError::EmptyInput)?;
+        rest.iter()
+            .try_fold(*first, |sum, com| sum.checked_add(com))
+    }
+
+    /// Sum the partial witnesses of the slices of a vector at the same
+    /// position into its witness at that position
+    pub fn combine_partial_witnesses(partials: &[E::G1Projective]) -> E::G1Projective {
+        partials.iter().sum()
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Commitment<E, N> {
+    /// Commit to the slice of the inputs that starts at position `offset`
+    pub fn partial_commit(
+        pp: &ProverParam<E, N>,
+        offset: usize,
+        inputs: &[E::Fr],
+    ) -> Result<Self, PointproofError> {
+        Ok(Self {
+            inner: DynCommitment::partial_commit(&pp.inner, offset, inputs)?,
+        })
+    }
+
+    /// Open the slice of the inputs that starts at position `offset` at a given position
+    pub fn partial_open(
+        pp: &ProverParam<E, N>,
+        offset: usize,
+        inputs: &[E::Fr],
+        pos: usize,
+    ) -> Result<E::G1Projective, PointproofError> {
+        DynCommitment::partial_open(&pp.inner, offset, inputs, pos)
+    }
+
+    /// Sum the partial commitments of the slices of a vector into its
+    /// commitment. There must be at least one partial commitment.
+    pub fn combine_partial_commitments(partials: &[Self]) -> Result<Self, PointproofError> {
+        let partials: Vec<DynCommitment<E>> = partials.iter().map(|com| com.inner).collect();
+        Ok(Self {
+            inner: DynCommitment::combine_partial_commitments(&partials)?,
+        })
+    }
+
+    /// Sum the partial witnesses of the slices of a vector at the same
+    /// position into its witness at that position
+    pub fn combine_partial_witnesses(partials: &[E::G1Projective]) -> E::G1Projective {
+        DynCommitment::<E>::combine_partial_witnesses(partials)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::param::VerifierParam;
+    use crate::CommitmentScheme;
+    use crate::DynStructuredReferenceString;
+    use crate::StructuredReferenceString;
+    use ark_bn254::Bn254;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+    use ark_std::Zero;
+
+    const NUM_TEST: usize = 10;
+
+    macro_rules! test_distributed_commit_opening {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            let message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim - 3)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            // uneven slices, including an empty one
+            let bounds = [0, 5, 5, $dim / 2, $dim - 3];
+            let slices: Vec<(usize, &[<$engine as PairingEngine>::Fr])> = bounds
+                .windows(2)
+                .map(|w| (w[0], &message[w[0]..w[1]]))
+                .collect();
+
+            let partials: Vec<Commitment<$engine, $dim>> = slices
+                .iter()
+                .map(|&(offset, slice)| {
+                    Commitment::<$engine, $dim>::partial_commit(&prover_param, offset, slice)
+                        .unwrap()
+                })
+                .collect();
+            let commitment =
+                Commitment::<$engine, $dim>::combine_partial_commitments(&partials).unwrap();
+            assert_eq!(
+                commitment,
+                Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap()
+            );
+            let dyn_partials: Vec<DynCommitment<$engine>> =
+                partials.iter().map(|&com| com.into()).collect();
+            assert_eq!(
+                DynCommitment::combine_partial_commitments(&dyn_partials).unwrap(),
+                commitment.into()
+            );
+
+            let mut positions: Vec<usize> = (0..NUM_TEST)
+                .map(|_| (rng.next_u32() % $dim) as usize)
+                .collect();
+            positions.extend([0, 5, $dim - 1]);
+            for pos in positions {
+                let partials: Vec<<$engine as PairingEngine>::G1Projective> = slices
+                    .iter()
+                    .map(|&(offset, slice)| {
+                        Commitment::<$engine, $dim>::partial_open(&prover_param, offset, slice, pos)
+                            .unwrap()
+                    })
+                    .collect();
+                let witness = Commitment::<$engine, $dim>::combine_partial_witnesses(&partials);
+                assert_eq!(
+                    witness,
+                    Commitment::<$engine, $dim>::open(&prover_param, &message, pos).unwrap()
+                );
+                let input = message.get(pos).copied().unwrap_or_else(Zero::zero);
+                assert!(commitment
+                    .verify(&verifier_param, &input, pos, &witness)
+                    .unwrap());
+            }
+
+            // slices that do not fit, and partial commitments of other dimensions
+            assert!(matches!(
+                Commitment::<$engine, $dim>::partial_commit(&prover_param, $dim - 2, &message[..3]),
+                Err(PointproofError::InputTooLong { .. })
+            ));
+            assert!(matches!(
+                Commitment::<$engine, $dim>::partial_open(
+                    &prover_param,
+                    usize::MAX,
+                    &message[..1],
+                    0
+                ),
+                Err(PointproofError::InputTooLong { .. })
+            ));
+            assert!(matches!(
+                Commitment::<$engine, $dim>::partial_open(&prover_param, 0, &message, $dim),
+                Err(PointproofError::PositionOutOfRange { .. })
+            ));
+            let dyn_srs =
+                DynStructuredReferenceString::<$engine>::new_srs_for_testing($dim / 2, &mut rng)
+                    .unwrap();
+            let short_partial =
+                DynCommitment::partial_commit(&(&dyn_srs).into(), 0, &message[..1]).unwrap();
+            assert!(matches!(
+                DynCommitment::combine_partial_commitments(&[dyn_partials[0], short_partial]),
+                Err(PointproofError::DimensionMismatch { .. })
+            ));
+            assert!(matches!(
+                DynCommitment::<$engine>::combine_partial_commitments(&[]),
+                Err(PointproofError::EmptyInput)
+            ));
+            assert!(matches!(
+                Commitment::<$engine, $dim>::combine_partial_commitments(&[]),
+                Err(PointproofError::EmptyInput)
+            ));
+        };
+    }
+
+    #[test]
+    fn test_distributed_commit_opening() {
+        test_distributed_commit_opening!(Bn254, 64, "bn254");
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt;
+
+/// Errors returned by the commitment, opening and verification APIs
+#[derive(Debug)]
+pub enum PointproofError {
+    /// A position is not smaller than the vector length
+    PositionOutOfRange { pos: usize, n: usize },
+    /// A list of inputs is longer than the vector length
+    InputTooLong { len: usize, n: usize },
+    /// A polynomial has more coefficients than the supported powers
+    DegreeTooLarge { degree: usize, num_powers: usize },
+    /// Lists that are expected to be of the same length are not
+    LengthMismatch { expected: usize, actual: usize },
+    /// A list that needs at least one element is empty
+    EmptyInput,
+    /// A commitment or parameter is for vectors of another dimension
+    DimensionMismatch { expected: usize, actual: usize },
+    /// A parameter is of a dimension below the minimum an operation supports
+    DimensionTooSmall { n: usize, min: usize },
+    /// Not enough entropy was provided to generate parameters
+    InsufficientEntropy { len: usize, min: usize },
+    /// The parameters are not a valid SRS
+    MalformedParameters(String),
+    /// An error from the underlying KZG implementation
+    Kzg(ark_poly_commit::Error),
+}
+
+impl fmt::Display for PointproofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PositionOutOfRange { pos, n } => {
+                write!(f, "position {} is out of range for dimension {}", pos, n)
+            }
+            Self::InputTooLong { len, n } => {
+                write!(f, "{} inputs exceed the dimension {}", len, n)
+            }
+            Self::DegreeTooLarge { degree, num_powers } => write!(
+                f,
+                "degree {} is too large for {} powers",
+                degree, num_powers
+            ),
+            Self::LengthMismatch { expected, actual } => {
+                write!(f, "expected {} elements, got {}", expected, actual)
+            }
+            Self::EmptyInput => write!(f, "expected at least one element, got none"),
+            Self::DimensionMismatch { expected, actual } => {
+                write!(f, "expected dimension {}, got {}", expected, actual)
+            }
+            Self::DimensionTooSmall { n, min } => {
+                write!(f, "dimension {} is below the minimum {}", n, min)
+            }
+            Self::InsufficientEntropy { len, min } => write!(
+                f,
+                "{} bytes of entropy are insufficient, at least {} are required",
+                len, min
+            ),
+            Self::MalformedParameters(reason) => write!(f, "malformed parameters: {}", reason),
+            Self::Kzg(e) => write!(f, "kzg error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for PointproofError {}
+
+impl From<ark_poly_commit::Error> for PointproofError {
+    fn from(e: ark_poly_commit::Error) -> Self {
+        Self::Kzg(e)
+    }
+}
+
+/// Check that a position is within a vector of dimension `n`
+pub(crate) fn check_position(pos: usize, n: usize) -> Result<(), PointproofError> {
+    if pos >= n {
+        return Err(PointproofError::PositionOutOfRange { pos, n });
+    }
+    Ok(())
+}
+
+/// Check that a list of inputs fits within a vector of dimension `n`
+pub(crate) fn check_input_length(len: usize, n: usize) -> Result<(), PointproofError> {
+    if len > n {
+        return Err(PointproofError::InputTooLong { len, n });
+    }
+    Ok(())
+}
+
+/// Check that two related lists have the same length
+pub(crate) fn check_same_length(expected: usize, actual: usize) -> Result<(), PointproofError> {
+    if expected != actual {
+        return Err(PointproofError::LengthMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+/// Check that a commitment or parameter of dimension `actual` is usable
+/// where dimension `expected` is required
+pub(crate) fn check_dimension(expected: usize, actual: usize) -> Result<(), PointproofError> {
+    if expected != actual {
+        return Err(PointproofError::DimensionMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+/// Check that a dimension `n` is at least `min`
+pub(crate) fn check_min_dimension(n: usize, min: usize) -> Result<(), PointproofError> {
+    if n < min {
+        return Err(PointproofError::DimensionTooSmall { n, min });
+    }
+    Ok(())
+}
+
+/// An error for parameters that are not a valid SRS
+pub(crate) fn malformed(reason: &str) -> PointproofError {
+    PointproofError::MalformedParameters(reason.to_string())
+}
@@ -0,0 +1,250 @@
+use crate::error::check_dimension;
+use crate::error::check_input_length;
+use crate::param::DynProverParam;
+use crate::param::DynVerifierParam;
+use crate::param::ProverParam;
+use crate::param::VerifierParam;
+use crate::Commitment;
+use crate::DynCommitment;
+use crate::PointproofError;
+use ark_ec::msm::VariableBaseMSM;
+use ark_ec::AffineCurve;
+use ark_ec::PairingEngine;
+use ark_ec::ProjectiveCurve;
+use ark_ff::FftField;
+use ark_ff::Field;
+use ark_ff::PrimeField;
+use ark_poly::EvaluationDomain;
+use ark_poly::Radix2EvaluationDomain;
+use ark_std::Zero;
+#[cfg(feature = "parallel")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "parallel")]
+use rayon::iter::IntoParallelRefIterator;
+#[cfg(feature = "parallel")]
+use rayon::iter::IntoParallelRefMutIterator;
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+use std::ops::Neg;
+
+// For public weights y, the proof of the inner product \sum_i y_i m_i is the
+// combination of the witnesses \sum_i y_i w_i = \sum_i \sum_j y_i m_j g[n - i + j],
+// since each w_i satisfies e(C, h[n - i - 1]) = t^{m_i} * e(w_i, H):
+//   e(C, \sum_i y_i h[n - i - 1]) * e(-proof, H) = t^{\sum_i y_i m_i}.
+// The scalar of g[n - l + 1 + s] in the proof, for l weights, is the coefficient
+// of degree s of m(X) * y_rev(X), where y_rev lists the weights in reverse order.
+impl<E: PairingEngine> DynCommitment<E> {
+    /// Open the inner product of the inputs with public weights
+    pub fn functional_open(
+        pp: &DynProverParam<E>,
+        inputs: &[E::Fr],
+        weights: &[E::Fr],
+    ) -> Result<E::G1Projective, PointproofError> {
+        let n = pp.dimension();
+        check_input_length(inputs.len(), n)?;
+        check_input_length(weights.len(), n)?;
+        if inputs.is_empty() || weights.is_empty() {
+            return Ok(E::G1Projective::zero());
+        }
+
+        let l = weights.len();
+        let scalars: Vec<<E::Fr as PrimeField>::BigInt> = correlate(inputs, weights)
+            .iter()
+            .map(|x| x.into_repr())
+            .collect();
+        Ok(VariableBaseMSM::multi_scalar_mul(
+            &pp.g[n - l + 1..n - l + 1 + scalars.len()],
+            &scalars,
+        ))
+    }
+
+    /// Verify that the inner product of the committed inputs with public
+    /// weights is `value`
+    pub fn functional_verify(
+        &self,
+        vp: &DynVerifierParam<E>,
+        weights: &[E::Fr],
+        value: &E::Fr,
+        proof: &E::G1Projective,
+    ) -> Result<bool, PointproofError> {
+        let n = self.n;
+        check_dimension(n, vp.dimension())?;
+        check_input_length(weights.len(), n)?;
+
+        let bases: Vec<E::G2Affine> = (0..weights.len()).map(|i| vp.h[n - i - 1]).collect();
+        let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+            weights.iter().map(|y| y.into_repr()).collect();
+        let h = VariableBaseMSM::multi_scalar_mul(&bases, &scalars);
+
+        // e(C, \sum_i y_i h[n - i - 1]) * e(-proof, H) = t^{value}
+        let pairing_prod_inputs = [
+            (self.commitment.into_affine().into(), h.into_affine().into()),
+            (
+                proof.neg().into_affine().into(),
+                E::G2Affine::prime_subgroup_generator().into(),
+            ),
+        ];
+        Ok(E::product_of_pairings(pairing_prod_inputs.iter()) == vp.t.pow(value.into_repr()))
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Commitment<E, N> {
+    /// Open the inner product of the inputs with public weights
+    pub fn functional_open(
+        pp: &ProverParam<E, N>,
+        inputs: &[E::Fr],
+        weights: &[E::Fr],
+    ) -> Result<E::G1Projective, PointproofError> {
+        DynCommitment::functional_open(&pp.inner, inputs, weights)
+    }
+
+    /// Verify that the inner product of the committed inputs with public
+    /// weights is `value`
+    pub fn functional_verify(
+        &self,
+        vp: &VerifierParam<E, N>,
+        weights: &[E::Fr],
+        value: &E::Fr,
+        proof: &E::G1Projective,
+    ) -> Result<bool, PointproofError> {
+        self.inner
+            .functional_verify(&vp.inner, weights, value, proof)
+    }
+}
+
+/// The coefficients of m(X) * y_rev(X), for non-empty inputs and weights
+fn correlate<F: FftField>(inputs: &[F], weights: &[F]) -> Vec<F> {
+    let len = inputs.len() + weights.len() - 1;
+    let domain = match Radix2EvaluationDomain::<F>::new(len) {
+        Some(domain) => domain,
+        // the field has no FFT domain this large, so multiply directly
+        None => {
+            let mut coeffs = vec![F::zero(); len];
+            for (k, y) in weights.iter().rev().enumerate() {
+                for (j, m) in inputs.iter().enumerate() {
+                    coeffs[j + k] += *y * m;
+                }
+            }
+            return coeffs;
+        }
+    };
+
+    let mut a = inputs.to_vec();
+    a.resize(domain.size(), F::zero());
+    let mut b: Vec<F> = weights.iter().rev().copied().collect();
+    b.resize(domain.size(), F::zero());
+    domain.fft_in_place(&mut a);
+    domain.fft_in_place(&mut b);
+    ark_std::cfg_iter_mut!(a)
+        .zip(ark_std::cfg_iter!(b))
+        .for_each(|(a, b)| *a *= b);
+    domain.ifft_in_place(&mut a);
+
+    a.truncate(len);
+    a
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CommitmentScheme;
+    use crate::StructuredReferenceString;
+    use ark_bn254::Bn254;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+    use ark_std::One;
+    use ark_std::UniformRand;
+
+    macro_rules! test_functional_opening {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            let mut message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let a = (rng.next_u32() % $dim) as usize;
+            let b = (a + 1 + (rng.next_u32() % ($dim - 1)) as usize) % $dim;
+            message[b] = message[a];
+            let commitment = Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap();
+
+            let one = <$engine as PairingEngine>::Fr::one();
+            let zero = <$engine as PairingEngine>::Fr::zero();
+            let random: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let mut equality = vec![zero; $dim];
+            equality[a] = one;
+            equality[b] = -one;
+            // a sum, a weighted total over a prefix, and an equality of two positions
+            for weights in [&vec![one; $dim][..], &random[..$dim / 2], &equality[..]] {
+                let value: <$engine as PairingEngine>::Fr = weights
+                    .iter()
+                    .zip(message.iter())
+                    .map(|(y, m)| *y * m)
+                    .sum();
+                let proof =
+                    Commitment::<$engine, $dim>::functional_open(&prover_param, &message, weights)
+                        .unwrap();
+                let expected: <$engine as PairingEngine>::G1Projective = weights
+                    .iter()
+                    .enumerate()
+                    .map(|(i, y)| {
+                        Commitment::<$engine, $dim>::open(&prover_param, &message, i)
+                            .unwrap()
+                            .mul(y.into_repr())
+                    })
+                    .sum();
+                assert_eq!(proof, expected);
+                assert!(commitment
+                    .functional_verify(&verifier_param, weights, &value, &proof)
+                    .unwrap());
+                assert!(!commitment
+                    .functional_verify(&verifier_param, weights, &(value + one), &proof)
+                    .unwrap());
+            }
+
+            // short inputs, and no weights
+            let proof =
+                Commitment::<$engine, $dim>::functional_open(&prover_param, &message[..3], &random)
+                    .unwrap();
+            let short = Commitment::<$engine, $dim>::commit(&prover_param, &message[..3]).unwrap();
+            let value: <$engine as PairingEngine>::Fr = random
+                .iter()
+                .zip(message[..3].iter())
+                .map(|(y, m)| *y * m)
+                .sum();
+            assert!(short
+                .functional_verify(&verifier_param, &random, &value, &proof)
+                .unwrap());
+            let proof =
+                Commitment::<$engine, $dim>::functional_open(&prover_param, &message, &[]).unwrap();
+            assert!(commitment
+                .functional_verify(&verifier_param, &[], &zero, &proof)
+                .unwrap());
+
+            let long_weights = vec![one; $dim + 1];
+            assert!(matches!(
+                Commitment::<$engine, $dim>::functional_open(
+                    &prover_param,
+                    &message,
+                    &long_weights
+                ),
+                Err(PointproofError::InputTooLong { .. })
+            ));
+            assert!(matches!(
+                commitment.functional_verify(&verifier_param, &long_weights, &zero, &proof),
+                Err(PointproofError::InputTooLong { .. })
+            ));
+        };
+    }
+
+    #[test]
+    fn test_functional_opening() {
+        test_functional_opening!(Bn254, 64, "bn254");
+    }
+}
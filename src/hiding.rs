@@ -0,0 +1,193 @@
+use crate::error::check_position;
+use crate::param::DynProverParam;
+use crate::param::ProverParam;
+use crate::Commitment;
+use crate::CommitmentScheme;
+use crate::DynCommitment;
+use crate::PointproofError;
+use ark_ec::msm::VariableBaseMSM;
+use ark_ec::AffineCurve;
+use ark_ec::PairingEngine;
+use ark_ff::PrimeField;
+use ark_ff::Zero;
+
+// A hiding commitment adds r * G to the commitment, and r * g[N - i - 1] to the
+// witness of position i, so the verification equation is left unchanged:
+//   e(C + r * G, alpha^{N-i} * H) = t^{m_i} * e(w_i + r * alpha^{N-i} * G, H).
+// The witness of position i is then fully determined by C and m_i, so it
+// reveals nothing about the inputs at the other positions.
+impl<E: PairingEngine> DynCommitment<E> {
+    /// Commit to a list of inputs with prover parameters and a blinding factor
+    pub fn hiding_commit(
+        pp: &DynProverParam<E>,
+        inputs: &[E::Fr],
+        blinding: &E::Fr,
+    ) -> Result<Self, PointproofError> {
+        let mut com = Self::commit(pp, inputs)?;
+        com.commitment += pp.g0.mul(blinding.into_repr());
+        Ok(com)
+    }
+
+    /// Open an input at a given position of a hiding commitment
+    pub fn hiding_open(
+        pp: &DynProverParam<E>,
+        inputs: &[E::Fr],
+        blinding: &E::Fr,
+        pos: usize,
+    ) -> Result<E::G1Projective, PointproofError> {
+        let n = pp.dimension();
+        check_position(pos, n)?;
+
+        Ok(Self::open(pp, inputs, pos)? + pp.g[n - pos - 1].mul(blinding.into_repr()))
+    }
+
+    /// Open the inputs at positions `set` of a hiding commitment with a
+    /// single aggregated proof, where `self` is the commitment to `inputs`
+    pub fn hiding_aggregate_open(
+        &self,
+        pp: &DynProverParam<E>,
+        inputs: &[E::Fr],
+        blinding: &E::Fr,
+        set: &[usize],
+    ) -> Result<E::G1Projective, PointproofError> {
+        let n = self.n;
+        let proof = self.aggregate_open(pp, inputs, set)?;
+
+        // the blinding part of the aggregated proof is r * \sum_i t_i * g[n - i - 1]
+        let values: Vec<E::Fr> = set
+            .iter()
+            .map(|&pos| inputs.get(pos).copied().unwrap_or_else(E::Fr::zero))
+            .collect();
+        let coeffs = self.aggregation_coefficients(set, &values);
+        let bases: Vec<E::G1Affine> = set.iter().map(|&pos| pp.g[n - pos - 1]).collect();
+        let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+            coeffs.iter().map(|t| (*t * blinding).into_repr()).collect();
+        Ok(proof + VariableBaseMSM::multi_scalar_mul(&bases, &scalars))
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Commitment<E, N> {
+    /// Commit to a list of inputs with prover parameters and a blinding factor
+    pub fn hiding_commit(
+        pp: &ProverParam<E, N>,
+        inputs: &[E::Fr],
+        blinding: &E::Fr,
+    ) -> Result<Self, PointproofError> {
+        Ok(Self {
+            inner: DynCommitment::hiding_commit(&pp.inner, inputs, blinding)?,
+        })
+    }
+
+    /// Open an input at a given position of a hiding commitment
+    pub fn hiding_open(
+        pp: &ProverParam<E, N>,
+        inputs: &[E::Fr],
+        blinding: &E::Fr,
+        pos: usize,
+    ) -> Result<E::G1Projective, PointproofError> {
+        DynCommitment::hiding_open(&pp.inner, inputs, blinding, pos)
+    }
+
+    /// Open the inputs at positions `set` of a hiding commitment with a
+    /// single aggregated proof, where `self` is the commitment to `inputs`
+    pub fn hiding_aggregate_open(
+        &self,
+        pp: &ProverParam<E, N>,
+        inputs: &[E::Fr],
+        blinding: &E::Fr,
+        set: &[usize],
+    ) -> Result<E::G1Projective, PointproofError> {
+        self.inner
+            .hiding_aggregate_open(&pp.inner, inputs, blinding, set)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::param::VerifierParam;
+    use crate::StructuredReferenceString;
+    use ark_bn254::Bn254;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    const NUM_TEST: usize = 10;
+
+    macro_rules! test_hiding_commit_opening {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            let mut message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let blinding = <$engine as PairingEngine>::Fr::rand(&mut rng);
+            let mut commitment =
+                Commitment::<$engine, $dim>::hiding_commit(&prover_param, &message, &blinding)
+                    .unwrap();
+            assert_ne!(
+                commitment,
+                Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap()
+            );
+
+            let set: Vec<usize> = (0..NUM_TEST)
+                .map(|_| (rng.next_u32() % $dim) as usize)
+                .collect();
+            let values: Vec<<$engine as PairingEngine>::Fr> =
+                set.iter().map(|&pos| message[pos]).collect();
+            let witnesses: Vec<<$engine as PairingEngine>::G1Projective> = set
+                .iter()
+                .map(|&pos| {
+                    Commitment::<$engine, $dim>::hiding_open(
+                        &prover_param,
+                        &message,
+                        &blinding,
+                        pos,
+                    )
+                    .unwrap()
+                })
+                .collect();
+            for ((&pos, value), witness) in set.iter().zip(values.iter()).zip(witnesses.iter()) {
+                assert!(commitment
+                    .verify(&verifier_param, value, pos, witness)
+                    .unwrap());
+            }
+
+            let proof = commitment
+                .aggregate_proofs(&set, &values, &witnesses)
+                .unwrap();
+            assert!(commitment
+                .aggregate_verify(&verifier_param, &set, &values, &proof)
+                .unwrap());
+            assert_eq!(
+                proof,
+                commitment
+                    .hiding_aggregate_open(&prover_param, &message, &blinding, &set)
+                    .unwrap()
+            );
+
+            // updates keep the blinding factor
+            let pos = set[0];
+            let new_input = <$engine as PairingEngine>::Fr::rand(&mut rng);
+            commitment
+                .update(&prover_param, pos, &message[pos], &new_input)
+                .unwrap();
+            message[pos] = new_input;
+            assert_eq!(
+                commitment,
+                Commitment::<$engine, $dim>::hiding_commit(&prover_param, &message, &blinding)
+                    .unwrap()
+            );
+        };
+    }
+
+    #[test]
+    fn test_hiding_commit_opening() {
+        test_hiding_commit_opening!(Bn254, 128, "bn254");
+    }
+}
@@ -0,0 +1,216 @@
+use crate::error::check_dimension;
+use crate::Commitment;
+use crate::DynCommitment;
+use crate::PointproofError;
+use ark_ec::PairingEngine;
+use ark_ec::ProjectiveCurve;
+use ark_ff::PrimeField;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Mul;
+use std::ops::MulAssign;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+
+// Commitments and witnesses are linear in the inputs: if C_1 and C_2 commit to
+// m_1 and m_2, and w_1 and w_2 are their witnesses at the same position, then
+// a * C_1 + b * C_2 commits to a * m_1 + b * m_2, and a * w_1 + b * w_2 is its
+// witness at that position. Witnesses are G1 points, so they are combined with
+// the group operations of `E::G1Projective`.
+impl<E: PairingEngine> DynCommitment<E> {
+    /// The commitment to the sum of the committed vectors
+    pub fn checked_add(&self, other: &Self) -> Result<Self, PointproofError> {
+        check_dimension(self.n, other.n)?;
+        Ok(Self {
+            n: self.n,
+            commitment: self.commitment + other.commitment,
+        })
+    }
+
+    /// The commitment to the difference of the committed vectors
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, PointproofError> {
+        check_dimension(self.n, other.n)?;
+        Ok(Self {
+            n: self.n,
+            commitment: self.commitment - other.commitment,
+        })
+    }
+}
+
+impl<E: PairingEngine> Neg for DynCommitment<E> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            n: self.n,
+            commitment: -self.commitment,
+        }
+    }
+}
+
+impl<E: PairingEngine> Mul<E::Fr> for DynCommitment<E> {
+    type Output = Self;
+
+    fn mul(mut self, scalar: E::Fr) -> Self {
+        self *= scalar;
+        self
+    }
+}
+
+impl<E: PairingEngine> MulAssign<E::Fr> for DynCommitment<E> {
+    fn mul_assign(&mut self, scalar: E::Fr) {
+        self.commitment = self.commitment.mul(scalar.into_repr());
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Add for Commitment<E, N> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl<E: PairingEngine, const N: usize> AddAssign for Commitment<E, N> {
+    fn add_assign(&mut self, other: Self) {
+        self.inner.commitment += other.inner.commitment;
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Sub for Commitment<E, N> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+impl<E: PairingEngine, const N: usize> SubAssign for Commitment<E, N> {
+    fn sub_assign(&mut self, other: Self) {
+        self.inner.commitment -= other.inner.commitment;
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Neg for Commitment<E, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self { inner: -self.inner }
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Mul<E::Fr> for Commitment<E, N> {
+    type Output = Self;
+
+    fn mul(mut self, scalar: E::Fr) -> Self {
+        self *= scalar;
+        self
+    }
+}
+
+impl<E: PairingEngine, const N: usize> MulAssign<E::Fr> for Commitment<E, N> {
+    fn mul_assign(&mut self, scalar: E::Fr) {
+        self.inner *= scalar;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::param::ProverParam;
+    use crate::param::VerifierParam;
+    use crate::CommitmentScheme;
+    use crate::DynStructuredReferenceString;
+    use crate::StructuredReferenceString;
+    use ark_bn254::Bn254;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+    use ark_std::Zero;
+
+    const NUM_TEST: usize = 10;
+
+    macro_rules! test_homomorphic_ops {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            // two shards of a vector, one of them shorter
+            let left: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let right: Vec<<$engine as PairingEngine>::Fr> = (0..$dim / 2)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let a = <$engine as PairingEngine>::Fr::rand(&mut rng);
+            let b = <$engine as PairingEngine>::Fr::rand(&mut rng);
+            let combined: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                .map(|i| a * left[i] - b * right.get(i).copied().unwrap_or_else(Zero::zero))
+                .collect();
+
+            let left_commitment =
+                Commitment::<$engine, $dim>::commit(&prover_param, &left).unwrap();
+            let right_commitment =
+                Commitment::<$engine, $dim>::commit(&prover_param, &right).unwrap();
+            let commitment = left_commitment * a - right_commitment * b;
+            assert_eq!(
+                commitment,
+                Commitment::<$engine, $dim>::commit(&prover_param, &combined).unwrap()
+            );
+            assert_eq!(commitment, -(right_commitment * b) + left_commitment * a);
+
+            for _ in 0..NUM_TEST {
+                let pos = (rng.next_u32() % $dim) as usize;
+                let left_witness =
+                    Commitment::<$engine, $dim>::open(&prover_param, &left, pos).unwrap();
+                let right_witness =
+                    Commitment::<$engine, $dim>::open(&prover_param, &right, pos).unwrap();
+                let witness = left_witness.mul(a.into_repr()) - right_witness.mul(b.into_repr());
+                assert!(commitment
+                    .verify(&verifier_param, &combined[pos], pos, &witness)
+                    .unwrap());
+            }
+
+            let mut sum = left_commitment;
+            sum += right_commitment;
+            sum -= left_commitment;
+            assert_eq!(sum, right_commitment);
+            let mut scaled = left_commitment;
+            scaled *= a;
+            assert_eq!(scaled, left_commitment * a);
+
+            // runtime dimensions are checked
+            let dyn_srs =
+                DynStructuredReferenceString::<$engine>::new_srs_for_testing($dim / 2, &mut rng)
+                    .unwrap();
+            let dyn_prover_param = (&dyn_srs).into();
+            let short_commitment = DynCommitment::commit(&dyn_prover_param, &right).unwrap();
+            let dyn_left: DynCommitment<$engine> = left_commitment.into();
+            let dyn_right: DynCommitment<$engine> = right_commitment.into();
+            assert_eq!(
+                dyn_left.checked_sub(&dyn_right).unwrap(),
+                (left_commitment - right_commitment).into()
+            );
+            assert_eq!(
+                dyn_left.checked_add(&dyn_right).unwrap(),
+                (left_commitment + right_commitment).into()
+            );
+            assert!(matches!(
+                dyn_left.checked_add(&short_commitment),
+                Err(PointproofError::DimensionMismatch { .. })
+            ));
+        };
+    }
+
+    #[test]
+    fn test_homomorphic_ops() {
+        test_homomorphic_ops!(Bn254, 64, "bn254");
+    }
+}
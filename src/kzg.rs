@@ -1,3 +1,4 @@
+use crate::PointproofError;
 use ark_ec::ProjectiveCurve;
 use ark_ec::{msm::VariableBaseMSM, PairingEngine};
 use ark_ff::PrimeField;
@@ -12,13 +13,22 @@ use std::ops::Div;
 
 /// Specializes the public parameters for a given maximum degree `d` for polynomials
 /// `d` should be less that `pp.max_degree()`.
-pub fn trim<E>(pp: &UniversalParams<E>, mut supported_degree: usize) -> (Powers<E>, VerifierKey<E>)
+pub fn trim<E>(
+    pp: &UniversalParams<E>,
+    mut supported_degree: usize,
+) -> Result<(Powers<'_, E>, VerifierKey<E>), PointproofError>
 where
     E: PairingEngine,
 {
     if supported_degree == 1 {
         supported_degree += 1;
     }
+    if supported_degree >= pp.powers_of_g.len() {
+        return Err(PointproofError::DegreeTooLarge {
+            degree: supported_degree,
+            num_powers: pp.powers_of_g.len(),
+        });
+    }
     let powers_of_g = pp.powers_of_g[..=supported_degree].to_vec();
     let powers_of_gamma_g = (0..=supported_degree)
         .map(|i| pp.powers_of_gamma_g[&i])
@@ -36,7 +46,7 @@ where
         prepared_h: pp.prepared_h.clone(),
         prepared_beta_h: pp.prepared_beta_h.clone(),
     };
-    (powers, vk)
+    Ok((powers, vk))
 }
 
 pub(crate) fn check_degree_is_too_large(degree: usize, num_powers: usize) -> bool {
@@ -44,22 +54,26 @@ pub(crate) fn check_degree_is_too_large(degree: usize, num_powers: usize) -> boo
     num_coefficients <= num_powers
 }
 
+fn check_degree(degree: usize, num_powers: usize) -> Result<(), PointproofError> {
+    if !check_degree_is_too_large(degree, num_powers) {
+        return Err(PointproofError::DegreeTooLarge { degree, num_powers });
+    }
+    Ok(())
+}
+
 fn open_with_witness_polynomial<E, P>(
     powers: &Powers<E>,
     point: P::Point,
     randomness: &Randomness<E::Fr, P>,
     witness_polynomial: &P,
     hiding_witness_polynomial: Option<&P>,
-) -> Proof<E>
+) -> Result<Proof<E>, PointproofError>
 where
     E: PairingEngine,
     P: UVPolynomial<E::Fr, Point = E::Fr>,
     for<'a, 'b> &'a P: Div<&'b P, Output = P>,
 {
-    assert!(
-        check_degree_is_too_large(witness_polynomial.degree(), powers.size()),
-        "degree is too large"
-    );
+    check_degree(witness_polynomial.degree(), powers.size())?;
     let (num_leading_zeros, witness_coeffs) =
         skip_leading_zeros_and_convert_to_bigints(witness_polynomial);
 
@@ -76,7 +90,7 @@ where
         let blinding_evaluation = blinding_p.evaluate(&point);
         end_timer!(blinding_eval_time);
 
-        let random_witness_coeffs = convert_to_bigints(&hiding_witness_polynomial.coeffs());
+        let random_witness_coeffs = convert_to_bigints(hiding_witness_polynomial.coeffs());
         let witness_comm_time =
             start_timer!(|| "Computing commitment to random witness polynomial");
         w += &VariableBaseMSM::multi_scalar_mul(&powers.powers_of_gamma_g, &random_witness_coeffs);
@@ -86,10 +100,10 @@ where
         None
     };
 
-    Proof {
+    Ok(Proof {
         w: w.into_affine(),
         random_v,
-    }
+    })
 }
 
 /// On input a polynomial `p` and a point `point`, outputs a proof for the same.
@@ -98,22 +112,19 @@ pub fn open<E, P>(
     p: &P,
     point: P::Point,
     rand: &Randomness<E::Fr, P>,
-) -> Proof<E>
+) -> Result<Proof<E>, PointproofError>
 where
     E: PairingEngine,
     P: UVPolynomial<E::Fr, Point = E::Fr>,
     for<'a, 'b> &'a P: Div<&'b P, Output = P>,
 {
-    assert!(
-        check_degree_is_too_large(p.degree(), powers.size()),
-        "degree is too large"
-    );
+    check_degree(p.degree(), powers.size())?;
 
     let open_time = start_timer!(|| format!("Opening polynomial of degree {}", p.degree()));
 
     let witness_time = start_timer!(|| "Computing witness polynomials");
     let (witness_poly, hiding_witness_poly) =
-        KZG10::<E, P>::compute_witness_polynomial(p, point, rand).unwrap();
+        KZG10::<E, P>::compute_witness_polynomial(p, point, rand)?;
     end_timer!(witness_time);
 
     let proof = open_with_witness_polynomial(
@@ -122,10 +133,10 @@ where
         rand,
         &witness_poly,
         hiding_witness_poly.as_ref(),
-    );
+    )?;
 
     end_timer!(open_time);
-    proof
+    Ok(proof)
 }
 
 fn skip_leading_zeros_and_convert_to_bigints<F: PrimeField, P: UVPolynomial<F>>(
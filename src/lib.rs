@@ -1,19 +1,93 @@
 #![allow(dead_code)]
 
+mod aggregate;
+mod batch;
+mod bound;
+mod ceremony;
+mod distributed;
+mod error;
+mod functional;
+mod hiding;
+mod homomorphic;
 mod kzg;
+mod message;
+mod open_all;
 mod param;
+mod paramgen;
+mod ptau;
+mod serialize;
+mod sparse;
+mod state;
+mod transcript;
+mod update;
 mod vc;
+mod verkle;
 
 use ark_ec::PairingEngine;
+pub use ceremony::Contribution;
+pub use error::PointproofError;
 pub use kzg::*;
+pub use message::hash_to_field;
+pub use message::pairing_plus_hash_to_field;
+pub use param::check_dyn_parameters;
+pub use param::check_parameters;
+pub use param::DynProverParam;
+pub use param::DynStructuredReferenceString;
+pub use param::DynVerifierParam;
 pub use param::ProverParam;
 pub use param::StructuredReferenceString;
 pub use param::VerifierParam;
+pub use paramgen::load_dyn_paramgen;
+pub use paramgen::load_paramgen;
+pub use ptau::PtauStart;
+pub use state::DynProverState;
+pub use state::ProverState;
+pub use transcript::Transcript;
+pub use verkle::DynVerkleTree;
+pub use verkle::VerkleProof;
+pub use verkle::VerkleTree;
 
-pub struct Commitment<E: PairingEngine, const N: usize> {
+/// A commitment to a vector whose dimension is only known at runtime
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DynCommitment<E: PairingEngine> {
+    n: usize,
     commitment: E::G1Projective,
 }
 
+/// A `DynCommitment` to a vector of dimension `N`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Commitment<E: PairingEngine, const N: usize> {
+    inner: DynCommitment<E>,
+}
+
+impl<E: PairingEngine> DynCommitment<E> {
+    /// The dimension of the committed vector
+    pub fn dimension(&self) -> usize {
+        self.n
+    }
+}
+
+impl<E: PairingEngine, const N: usize> From<Commitment<E, N>> for DynCommitment<E> {
+    fn from(com: Commitment<E, N>) -> Self {
+        com.inner
+    }
+}
+
+impl<E: PairingEngine, const N: usize> TryFrom<DynCommitment<E>> for Commitment<E, N> {
+    type Error = PointproofError;
+
+    fn try_from(com: DynCommitment<E>) -> Result<Self, PointproofError> {
+        error::check_dimension(N, com.n)?;
+        Ok(Self { inner: com })
+    }
+}
+
+impl<E: PairingEngine, const N: usize> AsRef<DynCommitment<E>> for Commitment<E, N> {
+    fn as_ref(&self) -> &DynCommitment<E> {
+        &self.inner
+    }
+}
+
 pub trait CommitmentScheme {
     type ProverParam;
     type VerifierParam;
@@ -22,10 +96,19 @@ pub trait CommitmentScheme {
     type Witness;
 
     /// Commit to a list of inputs with prover parameters
-    fn commit(pp: &Self::ProverParam, inputs: &[Self::MessageUnit]) -> Self;
+    fn commit(
+        pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+    ) -> Result<Self, PointproofError>
+    where
+        Self: Sized;
 
     /// Open an input at a given position
-    fn open(pp: &Self::ProverParam, inputs: &[Self::MessageUnit], pos: usize) -> Self::Witness;
+    fn open(
+        pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+        pos: usize,
+    ) -> Result<Self::Witness, PointproofError>;
 
     /// Verify the input/witness pair is correct
     fn verify(
@@ -34,5 +117,5 @@ pub trait CommitmentScheme {
         input: &Self::MessageUnit,
         pos: usize,
         witness: &Self::Witness,
-    ) -> bool;
+    ) -> Result<bool, PointproofError>;
 }
@@ -0,0 +1,241 @@
+use crate::param::DynProverParam;
+use crate::param::DynVerifierParam;
+use crate::param::ProverParam;
+use crate::param::VerifierParam;
+use crate::Commitment;
+use crate::CommitmentScheme;
+use crate::DynCommitment;
+use crate::PointproofError;
+use ark_ec::PairingEngine;
+use ark_ff::PrimeField;
+#[cfg(feature = "parallel")]
+use rayon::iter::IntoParallelRefIterator;
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+use sha2::Digest;
+use sha2::Sha512;
+
+/// Domain separator for hashing byte messages to scalars
+const MESSAGE_DOMAIN: &[u8] = b"pointproofs message";
+
+/// Size of the output keying material that pairing-plus reduces to a scalar
+const OKM_SIZE: usize = 48;
+
+/// Hash a byte message to a scalar: the 64-byte SHA-512 digest of
+/// `"pointproofs message" || message` is read as a big-endian integer and
+/// reduced modulo the order of the scalar field.
+pub fn hash_to_field<F: PrimeField>(message: &[u8]) -> F {
+    let digest = Sha512::new()
+        .chain_update(MESSAGE_DOMAIN)
+        .chain_update(message)
+        .finalize();
+    F::from_be_bytes_mod_order(&digest)
+}
+
+/// Hash a byte message to a scalar without domain separation, for
+/// compatibility with Algorand's pairing-plus: the first 48 bytes of the
+/// SHA-512 digest of the raw message are mapped with the `Fr::from_okm`
+/// reduction of pairing-plus, i.e., read as a big-endian integer and reduced
+/// modulo the order of the scalar field. A zero result is returned as is.
+pub fn pairing_plus_hash_to_field<F: PrimeField>(message: &[u8]) -> F {
+    from_okm(&Sha512::digest(message)[..OKM_SIZE])
+}
+
+/// The `from_okm` map of pairing-plus from 48 bytes to a scalar
+fn from_okm<F: PrimeField>(okm: &[u8]) -> F {
+    F::from_be_bytes_mod_order(okm)
+}
+
+fn hash_messages<F: PrimeField, M: AsRef<[u8]> + Sync>(messages: &[M]) -> Vec<F> {
+    ark_std::cfg_iter!(messages)
+        .map(|message| hash_to_field(message.as_ref()))
+        .collect()
+}
+
+// Byte messages are committed to through their hashes, so positions beyond
+// the list of messages are still zero scalars rather than hashes of empty messages.
+impl<E: PairingEngine> DynCommitment<E> {
+    /// Commit to a list of byte messages with prover parameters
+    pub fn commit_bytes<M: AsRef<[u8]> + Sync>(
+        pp: &DynProverParam<E>,
+        messages: &[M],
+    ) -> Result<Self, PointproofError> {
+        Self::commit(pp, &hash_messages(messages))
+    }
+
+    /// Open the byte message at a given position
+    pub fn open_bytes<M: AsRef<[u8]> + Sync>(
+        pp: &DynProverParam<E>,
+        messages: &[M],
+        pos: usize,
+    ) -> Result<E::G1Projective, PointproofError> {
+        Self::open(pp, &hash_messages(messages), pos)
+    }
+
+    /// Verify the byte message/witness pair is correct
+    pub fn verify_bytes(
+        &self,
+        vp: &DynVerifierParam<E>,
+        message: &[u8],
+        pos: usize,
+        witness: &E::G1Projective,
+    ) -> Result<bool, PointproofError> {
+        self.verify(vp, &hash_to_field(message), pos, witness)
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Commitment<E, N> {
+    /// Commit to a list of byte messages with prover parameters
+    pub fn commit_bytes<M: AsRef<[u8]> + Sync>(
+        pp: &ProverParam<E, N>,
+        messages: &[M],
+    ) -> Result<Self, PointproofError> {
+        Ok(Self {
+            inner: DynCommitment::commit_bytes(&pp.inner, messages)?,
+        })
+    }
+
+    /// Open the byte message at a given position
+    pub fn open_bytes<M: AsRef<[u8]> + Sync>(
+        pp: &ProverParam<E, N>,
+        messages: &[M],
+        pos: usize,
+    ) -> Result<E::G1Projective, PointproofError> {
+        DynCommitment::open_bytes(&pp.inner, messages, pos)
+    }
+
+    /// Verify the byte message/witness pair is correct
+    pub fn verify_bytes(
+        &self,
+        vp: &VerifierParam<E, N>,
+        message: &[u8],
+        pos: usize,
+        witness: &E::G1Projective,
+    ) -> Result<bool, PointproofError> {
+        self.inner.verify_bytes(&vp.inner, message, pos, witness)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::StructuredReferenceString;
+    use ark_bn254::Bn254;
+    use ark_bn254::Fr;
+    use ark_ff::Field;
+    use ark_ff::Zero;
+    use ark_std::rand::RngCore;
+    use ark_std::str::FromStr;
+    use ark_std::test_rng;
+
+    const NUM_TEST: usize = 10;
+
+    #[test]
+    fn test_hash_to_field() {
+        // the digest is reduced as a big-endian integer
+        let digest = Sha512::new()
+            .chain_update(b"pointproofs message")
+            .chain_update(b"abc")
+            .finalize();
+        let mut expected = Fr::from(0u64);
+        for byte in digest.iter() {
+            expected = expected * Fr::from(256u64) + Fr::from(*byte as u64);
+        }
+        assert_eq!(hash_to_field::<Fr>(b"abc"), expected);
+        assert_ne!(hash_to_field::<Fr>(b""), hash_to_field::<Fr>(b"\0"));
+
+        // the domain separator is hashed in front of the message
+        assert_ne!(
+            hash_to_field::<Fr>(b"abc"),
+            pairing_plus_hash_to_field::<Fr>(b"abc")
+        );
+    }
+
+    #[test]
+    fn test_pairing_plus_hash_to_field() {
+        use ark_bls12_381::Fr;
+
+        // outputs of Fr::from_okm of pairing-plus 0.19 on the first 48 bytes
+        // of SHA-512(message)
+        let known_answers: [(&[u8], &str); 3] = [
+            (
+                b"",
+                "11204531607846879432993594189023569841750167302772959970481820060842809578804",
+            ),
+            (
+                b"abc",
+                "50323052769472549602366288767630850592362510100652488332986474712235842101508",
+            ),
+            (
+                b"pointproofs",
+                "43845641450835283412993661007051943869966532761667517687519899457936480121484",
+            ),
+        ];
+        for (message, expected) in known_answers.iter() {
+            assert_eq!(
+                pairing_plus_hash_to_field::<Fr>(message),
+                Fr::from_str(expected).unwrap()
+            );
+        }
+
+        // outputs of Fr::from_okm of pairing-plus 0.19 on the largest input,
+        // and on the modulus, which maps to zero
+        assert_eq!(
+            from_okm::<Fr>(&[0xff; OKM_SIZE]),
+            Fr::from_str(
+                "20690987792304517493546419304065979215229097455316523017309531943206242971948"
+            )
+            .unwrap()
+        );
+        let mut modulus = vec![0u8; OKM_SIZE - 32];
+        for limb in Fr::characteristic().iter().rev() {
+            modulus.extend(limb.to_be_bytes());
+        }
+        assert!(from_okm::<Fr>(&modulus).is_zero());
+    }
+
+    macro_rules! test_bytes_commit_opening {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            let messages: Vec<String> = (0..$dim).map(|i| format!("record {}", i)).collect();
+            let commitment =
+                Commitment::<$engine, $dim>::commit_bytes(&prover_param, &messages).unwrap();
+            let scalars: Vec<<$engine as PairingEngine>::Fr> = messages
+                .iter()
+                .map(|message| hash_to_field(message.as_bytes()))
+                .collect();
+            assert_eq!(
+                commitment,
+                Commitment::<$engine, $dim>::commit(&prover_param, &scalars).unwrap()
+            );
+
+            for _ in 0..NUM_TEST {
+                let pos = (rng.next_u32() % $dim) as usize;
+                let witness =
+                    Commitment::<$engine, $dim>::open_bytes(&prover_param, &messages, pos).unwrap();
+                assert!(commitment
+                    .verify_bytes(&verifier_param, messages[pos].as_bytes(), pos, &witness)
+                    .unwrap());
+                assert!(!commitment
+                    .verify_bytes(&verifier_param, b"another record", pos, &witness)
+                    .unwrap());
+            }
+
+            assert!(matches!(
+                Commitment::<$engine, $dim>::open_bytes(&prover_param, &messages, $dim),
+                Err(PointproofError::PositionOutOfRange { .. })
+            ));
+        };
+    }
+
+    #[test]
+    fn test_bytes_commit_opening() {
+        test_bytes_commit_opening!(Bn254, 128, "bn254");
+    }
+}
@@ -0,0 +1,144 @@
+use crate::error::check_input_length;
+use crate::param::DynProverParam;
+use crate::param::ProverParam;
+use crate::Commitment;
+use crate::CommitmentScheme;
+use crate::DynCommitment;
+use crate::PointproofError;
+use ark_ec::AffineCurve;
+use ark_ec::PairingEngine;
+use ark_poly::EvaluationDomain;
+use ark_poly::Radix2EvaluationDomain;
+use ark_std::Zero;
+#[cfg(feature = "parallel")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "parallel")]
+use rayon::iter::IntoParallelIterator;
+#[cfg(feature = "parallel")]
+use rayon::iter::IntoParallelRefIterator;
+#[cfg(feature = "parallel")]
+use rayon::iter::IntoParallelRefMutIterator;
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+
+// The witness of position i is w_i = \sum_j m_j * g[n - i + j], so the vector of
+// witnesses is the product of the Toeplitz matrix T[i][j] = g[n + j - i] with the
+// inputs. As in FK20 (https://eprint.iacr.org/2023/033), T is embedded into a
+// circulant matrix of size k >= 2n, whose first column is
+//   a = [g[n], g[n - 1], ..., g[1], 0, ..., 0, g[2n - 1], ..., g[n + 1]],
+// and the circulant product is computed with FFTs over G1:
+//   w = IFFT(FFT(a) * FFT(m))[0..n],
+// i.e., O(k log k) G1 scalar multiplications, since every butterfly of an FFT
+// over G1 multiplies a point by a root of unity.
+impl<E: PairingEngine> DynCommitment<E> {
+    /// Open every position at once, i.e., return the witnesses of the
+    /// positions 0..n in the same form as `open`
+    pub fn open_all(
+        pp: &DynProverParam<E>,
+        inputs: &[E::Fr],
+    ) -> Result<Vec<E::G1Projective>, PointproofError> {
+        let n = pp.dimension();
+        check_input_length(inputs.len(), n)?;
+
+        let domain = match Radix2EvaluationDomain::<E::Fr>::new(n << 1) {
+            Some(domain) => domain,
+            // the field has no FFT domain this large, so open each position
+            None => {
+                return ark_std::cfg_into_iter!(0..n)
+                    .map(|pos| Self::open(pp, inputs, pos))
+                    .collect()
+            }
+        };
+        let k = domain.size();
+
+        // g[n] is the empty slot, so a[0] = 0
+        let mut a = vec![E::G1Projective::zero(); k];
+        for d in 1..n {
+            a[d] = pp.g[n - d].into_projective();
+            a[k - d] = pp.g[n + d].into_projective();
+        }
+        let mut scalars = inputs.to_vec();
+        scalars.resize(k, E::Fr::zero());
+
+        domain.fft_in_place(&mut a);
+        domain.fft_in_place(&mut scalars);
+        ark_std::cfg_iter_mut!(a)
+            .zip(ark_std::cfg_iter!(scalars))
+            .for_each(|(a, m)| *a *= *m);
+        domain.ifft_in_place(&mut a);
+
+        a.truncate(n);
+        Ok(a)
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Commitment<E, N> {
+    /// Open every position at once, i.e., return the witnesses of the
+    /// positions 0..N in the same form as `open`
+    pub fn open_all(
+        pp: &ProverParam<E, N>,
+        inputs: &[E::Fr],
+    ) -> Result<Vec<E::G1Projective>, PointproofError> {
+        DynCommitment::open_all(&pp.inner, inputs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::param::VerifierParam;
+    use crate::StructuredReferenceString;
+    use ark_bn254::Bn254;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    macro_rules! test_open_all {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            let message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            // also cover inputs shorter than the dimension
+            for inputs in [&message[..], &message[..$dim / 3]] {
+                let commitment =
+                    Commitment::<$engine, $dim>::commit(&prover_param, inputs).unwrap();
+                let witnesses =
+                    Commitment::<$engine, $dim>::open_all(&prover_param, inputs).unwrap();
+                assert_eq!(witnesses.len(), $dim);
+                for (pos, witness) in witnesses.iter().enumerate() {
+                    assert_eq!(
+                        *witness,
+                        Commitment::<$engine, $dim>::open(&prover_param, inputs, pos).unwrap()
+                    );
+                    let input = inputs
+                        .get(pos)
+                        .copied()
+                        .unwrap_or_else(<$engine as PairingEngine>::Fr::zero);
+                    assert!(commitment
+                        .verify(&verifier_param, &input, pos, witness)
+                        .unwrap());
+                }
+            }
+
+            let long_message = vec![<$engine as PairingEngine>::Fr::zero(); $dim + 1];
+            assert!(matches!(
+                Commitment::<$engine, $dim>::open_all(&prover_param, &long_message),
+                Err(PointproofError::InputTooLong { .. })
+            ));
+        };
+    }
+
+    #[test]
+    fn test_open_all() {
+        test_open_all!(Bn254, 128, "bn254");
+        // the FFT domain is larger than 2N
+        test_open_all!(Bn254, 100, "bn254");
+        test_open_all!(Bn254, 1, "bn254");
+    }
+}
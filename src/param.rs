@@ -1,32 +1,57 @@
+use crate::error::check_dimension;
+use crate::error::malformed;
+use crate::PointproofError;
+use ark_ec::msm::VariableBaseMSM;
 use ark_ec::AffineCurve;
 use ark_ec::PairingEngine;
 use ark_ec::ProjectiveCurve;
+use ark_ff::PrimeField;
 use ark_std::rand::Rng;
+use ark_std::One;
 use ark_std::UniformRand;
 use ark_std::Zero;
+use sha2::digest::core_api::BlockSizeUser;
+use sha2::digest::Output;
+use sha2::Digest;
+use sha2::Sha512;
+use std::ops::Neg;
+use zeroize::Zeroize;
 
 #[cfg(feature = "parallel")]
 use rayon::iter::IntoParallelRefIterator;
 #[cfg(feature = "parallel")]
 use rayon::iter::ParallelIterator;
 
-/// A StructuredReferenceString contains three components:
+/// Minimum number of bytes of entropy accepted by `StructuredReferenceString::setup`
+const MIN_SETUP_ENTROPY: usize = 32;
+
+/// Domain separator for deriving the trapdoor from the setup entropy
+const SETUP_DOMAIN: &[u8] = b"pointproof setup";
+
+/// A StructuredReferenceString contains four components:
+/// - g0 = G, the base of the blinding factor of hiding commitments
 /// - g = \[ alpha * G, alpha^2 * G,     alpha^3 G,      \dots,   alpha^{n} G,
-///           _,        alpha^{n+2} * G, alpha^{n+3} G, \dots,   alpha^{2n} G \]
+///   _,        alpha^{n+2} * G, alpha^{n+3} G, \dots,   alpha^{2n} G \]
 /// - h = \[ alpha * H, alpha^2 * H,     alpha^3 H,      \dots,   alpha^{n} H, \]
 /// - t = e(alpha^{n+1} * G, H)
+///
+/// The dimension n is only known at runtime, see `StructuredReferenceString`
+/// for a dimension fixed at compile time.
 #[derive(Debug, Clone, PartialEq, Default)]
-pub struct StructuredReferenceString<E: PairingEngine, const N: usize> {
+pub struct DynStructuredReferenceString<E: PairingEngine> {
+    pub(crate) g0: E::G1Affine,
     pub(crate) g: Vec<E::G1Affine>,
     pub(crate) h: Vec<E::G2Affine>,
     pub(crate) t: E::Fqk,
 }
 
 /// The prover parameter is a reference to the G1 coordinates of SRS:
+/// - g0 = G, the base of the blinding factor of hiding commitments
 /// - g = \[ alpha * G, alpha^2 * G,     alpha^3 G,      \dots,   alpha^{n} G,
-///           _,        alpha^{n+2} * G, alpha^{n+3} G, \dots,   alpha^{2n} G \]
+///   _,        alpha^{n+2} * G, alpha^{n+3} G, \dots,   alpha^{2n} G \]
 #[derive(Debug, Clone, PartialEq, Default)]
-pub struct ProverParam<E: PairingEngine, const N: usize> {
+pub struct DynProverParam<E: PairingEngine> {
+    pub(crate) g0: E::G1Affine,
     pub(crate) g: Vec<E::G1Affine>,
 }
 
@@ -34,28 +59,95 @@ pub struct ProverParam<E: PairingEngine, const N: usize> {
 /// - h = \[ alpha * H, alpha^2 * H,     alpha^3 H,      \dots,   alpha^{n} H, \]
 /// - t = e(alpha^{n+1} * G, H)
 #[derive(Debug, Clone, PartialEq, Default)]
-pub struct VerifierParam<E: PairingEngine, const N: usize> {
+pub struct DynVerifierParam<E: PairingEngine> {
     pub(crate) h: Vec<E::G2Affine>,
     pub(crate) t: E::Fqk,
 }
 
-impl<E: PairingEngine, const N: usize> StructuredReferenceString<E, N> {
-    pub fn new_srs_for_testing<R: Rng>(rng: &mut R) -> Self {
+/// A `DynStructuredReferenceString` of dimension `N`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StructuredReferenceString<E: PairingEngine, const N: usize> {
+    pub(crate) inner: DynStructuredReferenceString<E>,
+}
+
+/// A `DynProverParam` of dimension `N`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProverParam<E: PairingEngine, const N: usize> {
+    pub(crate) inner: DynProverParam<E>,
+}
+
+/// A `DynVerifierParam` of dimension `N`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VerifierParam<E: PairingEngine, const N: usize> {
+    pub(crate) inner: DynVerifierParam<E>,
+}
+
+impl<E: PairingEngine> DynStructuredReferenceString<E> {
+    /// The dimension of the committed vectors
+    pub fn dimension(&self) -> usize {
+        self.h.len()
+    }
+
+    /// Generate an SRS of dimension `n` with a trapdoor sampled from `rng`.
+    /// The trapdoor is not erased, so this is for testing only.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn new_srs_for_testing<R: Rng>(n: usize, rng: &mut R) -> Result<Self, PointproofError> {
+        Self::from_alpha(n, &E::Fr::rand(rng))
+    }
+
+    /// Generate an SRS of dimension `n` with a trapdoor derived from `entropy`, which must be at
+    /// least 32 bytes from a cryptographically secure source.
+    /// The trapdoor and all its powers are zeroized before returning; whoever holds
+    /// `entropy` can recompute the trapdoor, so it must be erased by the caller.
+    pub fn setup(n: usize, entropy: &[u8]) -> Result<Self, PointproofError> {
+        if entropy.len() < MIN_SETUP_ENTROPY {
+            return Err(PointproofError::InsufficientEntropy {
+                len: entropy.len(),
+                min: MIN_SETUP_ENTROPY,
+            });
+        }
+
+        let mut hasher = Sha512::new();
+        hasher.update(SETUP_DOMAIN);
+        hasher.update(entropy);
+        let mut digest = Output::<Sha512>::default();
+        hasher.finalize_into_reset(&mut digest);
+        wipe_hasher(&mut hasher);
+        let mut alpha = E::Fr::from_le_bytes_mod_order(&digest);
+        digest[..].zeroize();
+
+        let srs = if alpha.is_zero() {
+            Err(PointproofError::InsufficientEntropy {
+                len: entropy.len(),
+                min: MIN_SETUP_ENTROPY,
+            })
+        } else {
+            Self::from_alpha(n, &alpha)
+        };
+        alpha.zeroize();
+        srs
+    }
+
+    /// Generate an SRS of dimension `n` with trapdoor `alpha`, zeroizing the powers of `alpha`
+    fn from_alpha(n: usize, alpha: &E::Fr) -> Result<Self, PointproofError> {
+        if n == 0 {
+            return Err(malformed("the dimension must be positive"));
+        }
+
         // compute the alpha base as 1, alpha, alpha^2... alpha^{2n-1}
         // with alpha^n empty
-        let alpha = E::Fr::rand(rng);
-        let mut alpha_base = Vec::<E::Fr>::with_capacity(N << 1);
-        alpha_base.push(alpha);
-        for _ in 1..N << 1 {
-            alpha_base.push(alpha * alpha_base.last().unwrap())
+        let mut alpha_base = Vec::<E::Fr>::with_capacity(n << 1);
+        alpha_base.push(*alpha);
+        for _ in 1..n << 1 {
+            alpha_base.push(*alpha * alpha_base.last().unwrap())
         }
         // - t  = e(alpha^{n+1} * G, H)
         let t = E::pairing(
-            E::G1Affine::prime_subgroup_generator().mul(alpha_base[N]),
+            E::G1Affine::prime_subgroup_generator().mul(alpha_base[n]),
             E::G2Affine::prime_subgroup_generator(),
         );
 
-        alpha_base[N] = E::Fr::zero();
+        alpha_base[n] = E::Fr::zero();
 
         #[cfg(not(feature = "parallel"))]
         let (g, h) = {
@@ -69,7 +161,7 @@ impl<E: PairingEngine, const N: usize> StructuredReferenceString<E, N> {
             // - h = \[ H, alpha * H,       alpha^2 H,      \dots,   alpha^{n-1} H \]
             let h: Vec<E::G2Projective> = alpha_base
                 .iter()
-                .take(N)
+                .take(n)
                 .map(|&alpha_power| E::G2Affine::prime_subgroup_generator().mul(alpha_power))
                 .collect();
             (g, h)
@@ -85,7 +177,7 @@ impl<E: PairingEngine, const N: usize> StructuredReferenceString<E, N> {
                 .collect();
 
             // - h = \[ H, alpha * H,       alpha^2 H,      \dots,   alpha^{n-1} H \]
-            let h: Vec<E::G2Projective> = alpha_base[0..N]
+            let h: Vec<E::G2Projective> = alpha_base[0..n]
                 .par_iter()
                 .map(|&alpha_power| E::G2Affine::prime_subgroup_generator().mul(alpha_power))
                 .collect();
@@ -93,10 +185,65 @@ impl<E: PairingEngine, const N: usize> StructuredReferenceString<E, N> {
             (g, h)
         };
 
+        alpha_base.zeroize();
+
         let g = E::G1Projective::batch_normalization_into_affine(&g);
         let h = E::G2Projective::batch_normalization_into_affine(&h);
 
-        Self { g, h, t }
+        let g0 = E::G1Affine::prime_subgroup_generator();
+
+        Ok(Self { g0, g, h, t })
+    }
+}
+
+impl<E: PairingEngine, const N: usize> StructuredReferenceString<E, N> {
+    /// Generate an SRS with a trapdoor sampled from `rng`.
+    /// The trapdoor is not erased, so this is for testing only.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn new_srs_for_testing<R: Rng>(rng: &mut R) -> Result<Self, PointproofError> {
+        Ok(Self {
+            inner: DynStructuredReferenceString::new_srs_for_testing(N, rng)?,
+        })
+    }
+
+    /// Generate an SRS with a trapdoor derived from `entropy`,
+    /// see `DynStructuredReferenceString::setup`.
+    pub fn setup(entropy: &[u8]) -> Result<Self, PointproofError> {
+        Ok(Self {
+            inner: DynStructuredReferenceString::setup(N, entropy)?,
+        })
+    }
+}
+
+impl<E: PairingEngine> DynProverParam<E> {
+    /// The dimension of the committed vectors
+    pub fn dimension(&self) -> usize {
+        self.g.len() >> 1
+    }
+}
+
+impl<E: PairingEngine> DynVerifierParam<E> {
+    /// The dimension of the committed vectors
+    pub fn dimension(&self) -> usize {
+        self.h.len()
+    }
+}
+
+impl<'a, E: PairingEngine> From<&'a DynStructuredReferenceString<E>> for DynProverParam<E> {
+    fn from(srs: &'a DynStructuredReferenceString<E>) -> Self {
+        Self {
+            g0: srs.g0,
+            g: srs.g.to_vec(),
+        }
+    }
+}
+
+impl<'a, E: PairingEngine> From<&'a DynStructuredReferenceString<E>> for DynVerifierParam<E> {
+    fn from(srs: &'a DynStructuredReferenceString<E>) -> Self {
+        Self {
+            h: srs.h.to_vec(),
+            t: srs.t,
+        }
     }
 }
 
@@ -104,7 +251,9 @@ impl<'a, E: PairingEngine, const N: usize> From<&'a StructuredReferenceString<E,
     for ProverParam<E, N>
 {
     fn from(srs: &'a StructuredReferenceString<E, N>) -> Self {
-        Self { g: srs.g.to_vec() }
+        Self {
+            inner: (&srs.inner).into(),
+        }
     }
 }
 
@@ -113,39 +262,262 @@ impl<'a, E: PairingEngine, const N: usize> From<&'a StructuredReferenceString<E,
 {
     fn from(srs: &'a StructuredReferenceString<E, N>) -> Self {
         Self {
-            h: srs.h.to_vec(),
-            t: srs.t.clone(),
+            inner: (&srs.inner).into(),
+        }
+    }
+}
+
+/// Conversions between the parameters of dimension `N` and the parameters
+/// with a runtime dimension, which fail unless the dimension is `N`
+macro_rules! impl_dimension_conversion {
+    ($param: ident, $dyn_param: ident) => {
+        impl<E: PairingEngine, const N: usize> From<$param<E, N>> for $dyn_param<E> {
+            fn from(param: $param<E, N>) -> Self {
+                param.inner
+            }
+        }
+
+        impl<E: PairingEngine, const N: usize> TryFrom<$dyn_param<E>> for $param<E, N> {
+            type Error = PointproofError;
+
+            fn try_from(param: $dyn_param<E>) -> Result<Self, PointproofError> {
+                check_dimension(N, param.dimension())?;
+                Ok(Self { inner: param })
+            }
+        }
+
+        impl<E: PairingEngine, const N: usize> AsRef<$dyn_param<E>> for $param<E, N> {
+            fn as_ref(&self) -> &$dyn_param<E> {
+                &self.inner
+            }
         }
+    };
+}
+
+impl_dimension_conversion!(StructuredReferenceString, DynStructuredReferenceString);
+impl_dimension_conversion!(ProverParam, DynProverParam);
+impl_dimension_conversion!(VerifierParam, DynVerifierParam);
+
+/// Check that the prover and verifier parameters come from the same valid SRS,
+/// i.e., that
+/// - g holds consecutive powers of a same alpha, with the slot for alpha^{n+1} empty,
+/// - h holds consecutive powers of the same alpha, and
+/// - t = e(alpha^{n+1} * G, H).
+///
+/// The powers are checked with random linear combinations sampled from `rng`,
+/// within 6 pairings. An invalid SRS is reported as `MalformedParameters`.
+/// This function does not check that the group elements belong to the prime
+/// order subgroups.
+///
+/// See <https://github.com/algorand/pointproofs-paramgen> for the original algorithm.
+pub fn check_parameters<E: PairingEngine, R: Rng, const N: usize>(
+    prover_param: &ProverParam<E, N>,
+    verifier_param: &VerifierParam<E, N>,
+    rng: &mut R,
+) -> Result<(), PointproofError> {
+    check_dimension(N, verifier_param.inner.dimension())?;
+    check_dyn_parameters(&prover_param.inner, &verifier_param.inner, rng)
+}
+
+/// `check_parameters` for parameters of a runtime dimension
+pub fn check_dyn_parameters<E: PairingEngine, R: Rng>(
+    prover_param: &DynProverParam<E>,
+    verifier_param: &DynVerifierParam<E>,
+    rng: &mut R,
+) -> Result<(), PointproofError> {
+    let g = &prover_param.g;
+    let h = &verifier_param.h;
+    let n = verifier_param.dimension();
+    if n == 0 || g.len() != n << 1 || h.len() != n {
+        return Err(malformed("wrong number of powers"));
+    }
+    if !g[n].is_zero() {
+        return Err(malformed("the slot for alpha^{n+1} is not empty"));
+    }
+    if prover_param.g0.is_zero() || g[0].is_zero() {
+        return Err(malformed("the base or alpha is zero"));
+    }
+    let g2 = E::G2Affine::prime_subgroup_generator();
+
+    // g[i + 1] = alpha * g[i] for all i where neither slot is the empty one,
+    // and g[n + 1] = alpha^2 * g[n - 1] across the empty slot:
+    //   e(\sum_i r_i * g[i], h[0]) * e(r * g[n - 1], h[1])
+    //     = e(\sum_i r_i * g[i + 1] + r * g[n + 1], H)
+    let indices: Vec<usize> = (0..(n << 1) - 1)
+        .filter(|&i| i != n - 1 && i != n)
+        .collect();
+    let scalars: Vec<<E::Fr as PrimeField>::BigInt> = indices
+        .iter()
+        .map(|_| E::Fr::rand(rng).into_repr())
+        .collect();
+    let lhs_bases: Vec<E::G1Affine> = indices.iter().map(|&i| g[i]).collect();
+    let rhs_bases: Vec<E::G1Affine> = indices.iter().map(|&i| g[i + 1]).collect();
+    let lhs = VariableBaseMSM::multi_scalar_mul(&lhs_bases, &scalars);
+    let mut rhs = VariableBaseMSM::multi_scalar_mul(&rhs_bases, &scalars);
+
+    let mut pairing_prod_inputs = vec![(lhs.into_affine().into(), h[0].into())];
+    if n >= 2 {
+        let r = E::Fr::rand(rng);
+        pairing_prod_inputs.push((g[n - 1].mul(r).into_affine().into(), h[1].into()));
+        rhs += g[n + 1].mul(r);
     }
+    pairing_prod_inputs.push((rhs.neg().into_affine().into(), g2.into()));
+    if !E::product_of_pairings(pairing_prod_inputs.iter()).is_one() {
+        return Err(malformed("the G1 powers are inconsistent"));
+    }
+
+    // h[0] = alpha * H and h[i + 1] = alpha * h[i], where g[0] = alpha * G:
+    //   e(g[0], s * H + \sum_i s_i * h[i]) = e(G, s * h[0] + \sum_i s_i * h[i + 1])
+    let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+        (0..n).map(|_| E::Fr::rand(rng).into_repr()).collect();
+    let lhs_bases: Vec<E::G2Affine> = ark_std::iter::once(g2)
+        .chain(h[..n - 1].iter().copied())
+        .collect();
+    let lhs = VariableBaseMSM::multi_scalar_mul(&lhs_bases, &scalars);
+    let rhs = VariableBaseMSM::multi_scalar_mul(h, &scalars);
+    let pairing_prod_inputs = [
+        (g[0].into(), lhs.into_affine().into()),
+        (prover_param.g0.neg().into(), rhs.into_affine().into()),
+    ];
+    if !E::product_of_pairings(pairing_prod_inputs.iter()).is_one() {
+        return Err(malformed("the G2 powers are inconsistent"));
+    }
+
+    // t = e(alpha^n * G, alpha * H)
+    if E::pairing(g[n - 1], h[0]) != verifier_param.t {
+        return Err(malformed("t is inconsistent"));
+    }
+    Ok(())
+}
+
+/// Wipe a hasher that absorbed secret data, in place. `Sha512` does not implement
+/// `Zeroize`, and resetting it only restores the initial state and rewinds its buffer,
+/// so the buffered tail of the data is overwritten with zeros, which are then hashed
+/// so that the writes are not optimized away.
+pub(crate) fn wipe_hasher(hasher: &mut Sha512) {
+    Digest::reset(hasher);
+    hasher.update(vec![0u8; Sha512::block_size() - 1]);
+    let _ = hasher.finalize_reset();
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use ark_bn254::Bn254;
+    use ark_std::rand::RngCore;
     use ark_std::test_rng;
 
     macro_rules! test_srs_gen {
         ($engine: tt, $dim: expr, $disc: tt) => {
             let mut rng = test_rng();
 
-            let srs = StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng);
-            let _prover_param: ProverParam<$engine, $dim> = (&srs).into();
-            let _verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+            assert!(check_parameters(&prover_param, &verifier_param, &mut rng).is_ok());
+        };
+    }
+
+    macro_rules! test_setup {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let mut entropy = [0u8; 32];
+            rng.fill_bytes(&mut entropy);
+            let srs = StructuredReferenceString::<$engine, $dim>::setup(&entropy).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+            assert!(check_parameters(&prover_param, &verifier_param, &mut rng).is_ok());
+
+            // the setup is deterministic in the entropy
+            assert_eq!(
+                srs,
+                StructuredReferenceString::<$engine, $dim>::setup(&entropy).unwrap()
+            );
+            entropy[0] ^= 1;
+            assert_ne!(
+                srs,
+                StructuredReferenceString::<$engine, $dim>::setup(&entropy).unwrap()
+            );
+
+            assert!(matches!(
+                StructuredReferenceString::<$engine, $dim>::setup(&entropy[1..]),
+                Err(PointproofError::InsufficientEntropy { len: 31, min: 32 })
+            ));
+
+            // a wiped hasher is back to its initial state
+            let mut hasher = Sha512::new();
+            hasher.update(entropy);
+            wipe_hasher(&mut hasher);
+            assert_eq!(hasher.finalize(), Sha512::new().finalize());
+        };
+    }
+
+    macro_rules! test_check_parameters {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+            assert!(check_parameters(&prover_param, &verifier_param, &mut rng).is_ok());
+
+            // a G1 power off the chain
+            let mut bad_prover_param = prover_param.clone();
+            bad_prover_param.inner.g[$dim + 3] = bad_prover_param.inner.g[$dim + 2];
+            assert!(check_parameters(&bad_prover_param, &verifier_param, &mut rng).is_err());
+
+            // a G1 power right after the empty slot
+            let mut bad_prover_param = prover_param.clone();
+            bad_prover_param.inner.g[$dim + 1] = bad_prover_param.inner.g[$dim - 1];
+            assert!(check_parameters(&bad_prover_param, &verifier_param, &mut rng).is_err());
+
+            // the slot for alpha^{n+1} is not empty
+            let mut bad_prover_param = prover_param.clone();
+            bad_prover_param.inner.g[$dim] = bad_prover_param.inner.g[$dim - 1];
+            assert!(check_parameters(&bad_prover_param, &verifier_param, &mut rng).is_err());
+
+            // a G2 power off the chain
+            let mut bad_verifier_param = verifier_param.clone();
+            bad_verifier_param.inner.h[3] = bad_verifier_param.inner.h[2];
+            assert!(check_parameters(&prover_param, &bad_verifier_param, &mut rng).is_err());
+
+            // a wrong t
+            let mut bad_verifier_param = verifier_param.clone();
+            bad_verifier_param.inner.t *= verifier_param.inner.t;
+            assert!(check_parameters(&prover_param, &bad_verifier_param, &mut rng).is_err());
+
+            // prover and verifier parameters from different SRS
+            let other_srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let other_verifier_param: VerifierParam<$engine, $dim> = (&other_srs).into();
+            assert!(check_parameters(&prover_param, &other_verifier_param, &mut rng).is_err());
         };
     }
 
+    #[test]
+    fn test_empty_srs_gen() {
+        let mut rng = test_rng();
+        assert!(matches!(
+            StructuredReferenceString::<Bn254, 0>::new_srs_for_testing(&mut rng),
+            Err(PointproofError::MalformedParameters(_))
+        ));
+    }
+
     #[test]
     fn test_srs_gen() {
         test_srs_gen!(Bn254, 128, "bn254");
     }
 
-    // A faster algorithm to check the validity within 7 pairings.
-    // <https://github.com/algorand/pointproofs-paramgen>
-    pub(crate) fn check_parameters<E: PairingEngine, const N: usize>(
-        _prover_param: &ProverParam<E, N>,
-        _verifier_param: &VerifierParam<E, N>,
-    ) -> bool {
-        todo!()
+    #[test]
+    fn test_setup() {
+        test_setup!(Bn254, 128, "bn254");
+    }
+
+    #[test]
+    fn test_check_parameters() {
+        test_check_parameters!(Bn254, 128, "bn254");
     }
 }
@@ -0,0 +1,454 @@
+use crate::error::malformed;
+use crate::param::check_dyn_parameters;
+use crate::param::DynProverParam;
+use crate::param::DynVerifierParam;
+use crate::param::ProverParam;
+use crate::param::VerifierParam;
+use crate::PointproofError;
+use ark_bls12_381::Bls12_381;
+use ark_bls12_381::Fq;
+use ark_bls12_381::Fq12;
+use ark_bls12_381::Fq2;
+use ark_bls12_381::Fq6;
+use ark_bls12_381::G1Affine;
+use ark_bls12_381::G2Affine;
+use ark_ec::AffineCurve;
+use ark_ff::BigInteger384;
+use ark_ff::PrimeField;
+use ark_std::rand::Rng;
+use ark_std::Zero;
+use std::io::Read;
+
+// Parameters produced by <https://github.com/algorand/pointproofs-paramgen>
+// follow the serialization of Algorand's pointproofs library:
+// - prover parameters: ciphersuite (1 byte) || n (4 bytes, big endian)
+//       || 2n G1 elements, where the i-th element is alpha^{i+1} * G, and the
+//          element for alpha^{n+1} is the point at infinity
+// - verifier parameters: ciphersuite (1 byte) || n (4 bytes, big endian)
+//       || n G2 elements, where the i-th element is alpha^{i+1} * H
+//       || e(G, H)^{alpha^{n+1}}
+// This is the same index convention as the `g`/`h`/`t` layout of `ProverParam`
+// and `VerifierParam`, so the elements map one to one.
+//
+// Group elements use the zcash encoding of BLS12-381: big endian coordinates,
+// with the compression, infinity and sign flags in the 3 most significant bits
+// of the first byte, and the c1 coefficient of an Fq2 element first.
+// The GT element is 12 big endian Fq elements in the order
+// c0.c0.c0, c0.c0.c1, c0.c1.c0, ..., c1.c2.c1.
+// Any data after these elements (e.g., precomputed tables) is ignored.
+
+/// The ciphersuite identifier of BLS12-381 parameters
+const PARAMGEN_CIPHERSUITE: u8 = 0;
+
+/// Size of a big endian Fq element
+const FQ_SIZE: usize = 48;
+
+const COMPRESSION_FLAG: u8 = 0x80;
+const INFINITY_FLAG: u8 = 0x40;
+const SIGN_FLAG: u8 = 0x20;
+
+impl DynProverParam<Bls12_381> {
+    /// Read the prover parameters from the output of pointproofs-paramgen,
+    /// with the dimension recorded in the file.
+    /// Every element is checked to be in the prime order subgroup, but the powers
+    /// are not checked against each other; use `load_dyn_paramgen` for that.
+    pub fn from_paramgen<R: Read>(mut reader: R) -> Result<Self, PointproofError> {
+        let n = read_preamble(&mut reader)?;
+        let g = (0..n << 1)
+            .map(|_| read_g1(&mut reader))
+            .collect::<Result<Vec<G1Affine>, PointproofError>>()?;
+        if !g[n].is_zero() {
+            return Err(malformed("the slot for alpha^{n+1} is not empty"));
+        }
+
+        Ok(Self {
+            g0: G1Affine::prime_subgroup_generator(),
+            g,
+        })
+    }
+}
+
+impl DynVerifierParam<Bls12_381> {
+    /// Read the verifier parameters from the output of pointproofs-paramgen,
+    /// with the dimension recorded in the file.
+    /// Every element is checked to be in the prime order subgroup, but the powers
+    /// are not checked against each other; use `load_dyn_paramgen` for that.
+    pub fn from_paramgen<R: Read>(mut reader: R) -> Result<Self, PointproofError> {
+        let n = read_preamble(&mut reader)?;
+        let h = (0..n)
+            .map(|_| read_g2(&mut reader))
+            .collect::<Result<Vec<G2Affine>, PointproofError>>()?;
+        let t = read_fq12(&mut reader)?;
+
+        Ok(Self { h, t })
+    }
+}
+
+impl<const N: usize> ProverParam<Bls12_381, N> {
+    /// Read the prover parameters from the output of pointproofs-paramgen,
+    /// and fail unless their dimension is `N`.
+    /// Every element is checked to be in the prime order subgroup, but the powers
+    /// are not checked against each other; use `load_paramgen` for that.
+    pub fn from_paramgen<R: Read>(reader: R) -> Result<Self, PointproofError> {
+        DynProverParam::from_paramgen(reader)?.try_into()
+    }
+}
+
+impl<const N: usize> VerifierParam<Bls12_381, N> {
+    /// Read the verifier parameters from the output of pointproofs-paramgen,
+    /// and fail unless their dimension is `N`.
+    /// Every element is checked to be in the prime order subgroup, but the powers
+    /// are not checked against each other; use `load_paramgen` for that.
+    pub fn from_paramgen<R: Read>(reader: R) -> Result<Self, PointproofError> {
+        DynVerifierParam::from_paramgen(reader)?.try_into()
+    }
+}
+
+/// Read prover and verifier parameters of dimension `N` produced by
+/// pointproofs-paramgen, and check that they form a valid SRS.
+pub fn load_paramgen<P: Read, V: Read, R: Rng, const N: usize>(
+    prover_reader: P,
+    verifier_reader: V,
+    rng: &mut R,
+) -> Result<(ProverParam<Bls12_381, N>, VerifierParam<Bls12_381, N>), PointproofError> {
+    let (prover_param, verifier_param) = load_dyn_paramgen(prover_reader, verifier_reader, rng)?;
+    Ok((prover_param.try_into()?, verifier_param.try_into()?))
+}
+
+/// Read prover and verifier parameters produced by pointproofs-paramgen,
+/// with the dimension recorded in the files, and check that they form a valid SRS.
+pub fn load_dyn_paramgen<P: Read, V: Read, R: Rng>(
+    prover_reader: P,
+    verifier_reader: V,
+    rng: &mut R,
+) -> Result<(DynProverParam<Bls12_381>, DynVerifierParam<Bls12_381>), PointproofError> {
+    let prover_param = DynProverParam::from_paramgen(prover_reader)?;
+    let verifier_param = DynVerifierParam::from_paramgen(verifier_reader)?;
+    check_dyn_parameters(&prover_param, &verifier_param, rng)?;
+    Ok((prover_param, verifier_param))
+}
+
+/// Check the ciphersuite and return the dimension
+fn read_preamble<R: Read>(mut reader: R) -> Result<usize, PointproofError> {
+    let mut buf = [0u8; 5];
+    read_bytes(&mut reader, &mut buf)?;
+    if buf[0] != PARAMGEN_CIPHERSUITE {
+        return Err(malformed("unsupported ciphersuite"));
+    }
+    let n = u32::from_be_bytes(buf[1..].try_into().unwrap()) as usize;
+    if n == 0 {
+        return Err(malformed("the dimension must be positive"));
+    }
+    Ok(n)
+}
+
+fn read_bytes<R: Read>(mut reader: R, buf: &mut [u8]) -> Result<(), PointproofError> {
+    reader
+        .read_exact(buf)
+        .map_err(|_| malformed("the parameters are truncated"))
+}
+
+/// Parse a big endian Fq element, rejecting non-canonical encodings
+fn fq_from_be_bytes(bytes: &[u8]) -> Result<Fq, PointproofError> {
+    let mut limbs = [0u64; 6];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.rchunks(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    Fq::from_repr(BigInteger384(limbs)).ok_or_else(|| malformed("non-canonical field element"))
+}
+
+/// Read the flags of a zcash encoded point, and the rest of its encoding into `buf`,
+/// whose size is the uncompressed size of the point.
+/// Returns (compressed, infinity, sign), with the flags cleared in `buf`.
+fn read_point_bytes<R: Read>(
+    mut reader: R,
+    buf: &mut [u8],
+) -> Result<(bool, bool, bool), PointproofError> {
+    let compressed_size = buf.len() / 2;
+    read_bytes(&mut reader, &mut buf[..compressed_size])?;
+    let compressed = buf[0] & COMPRESSION_FLAG != 0;
+    let infinity = buf[0] & INFINITY_FLAG != 0;
+    let sign = buf[0] & SIGN_FLAG != 0;
+    buf[0] &= !(COMPRESSION_FLAG | INFINITY_FLAG | SIGN_FLAG);
+    if !compressed {
+        read_bytes(&mut reader, &mut buf[compressed_size..])?;
+    }
+
+    if infinity && (sign || buf.iter().any(|&b| b != 0)) {
+        return Err(malformed("invalid encoding of the point at infinity"));
+    }
+    if !compressed && sign {
+        return Err(malformed("sign flag set for an uncompressed point"));
+    }
+    Ok((compressed, infinity, sign))
+}
+
+fn read_g1<R: Read>(reader: R) -> Result<G1Affine, PointproofError> {
+    let mut buf = [0u8; 2 * FQ_SIZE];
+    let (compressed, infinity, sign) = read_point_bytes(reader, &mut buf)?;
+    if infinity {
+        return Ok(G1Affine::zero());
+    }
+
+    let x = fq_from_be_bytes(&buf[..FQ_SIZE])?;
+    let point = if compressed {
+        G1Affine::get_point_from_x(x, sign).ok_or_else(|| malformed("point not on the curve"))?
+    } else {
+        let y = fq_from_be_bytes(&buf[FQ_SIZE..])?;
+        let point = G1Affine::new(x, y, false);
+        if !point.is_on_curve() {
+            return Err(malformed("point not on the curve"));
+        }
+        point
+    };
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(malformed("point not in the prime order subgroup"));
+    }
+    Ok(point)
+}
+
+fn read_g2<R: Read>(reader: R) -> Result<G2Affine, PointproofError> {
+    let mut buf = [0u8; 4 * FQ_SIZE];
+    let (compressed, infinity, sign) = read_point_bytes(reader, &mut buf)?;
+    if infinity {
+        return Ok(G2Affine::zero());
+    }
+
+    let x = fq2_from_be_bytes(&buf[..2 * FQ_SIZE])?;
+    let point = if compressed {
+        G2Affine::get_point_from_x(x, sign).ok_or_else(|| malformed("point not on the curve"))?
+    } else {
+        let y = fq2_from_be_bytes(&buf[2 * FQ_SIZE..])?;
+        let point = G2Affine::new(x, y, false);
+        if !point.is_on_curve() {
+            return Err(malformed("point not on the curve"));
+        }
+        point
+    };
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(malformed("point not in the prime order subgroup"));
+    }
+    Ok(point)
+}
+
+/// Parse an Fq2 element encoded as c1 || c0
+fn fq2_from_be_bytes(bytes: &[u8]) -> Result<Fq2, PointproofError> {
+    let c1 = fq_from_be_bytes(&bytes[..FQ_SIZE])?;
+    let c0 = fq_from_be_bytes(&bytes[FQ_SIZE..])?;
+    Ok(Fq2::new(c0, c1))
+}
+
+fn read_fq12<R: Read>(mut reader: R) -> Result<Fq12, PointproofError> {
+    let mut buf = [0u8; 12 * FQ_SIZE];
+    read_bytes(&mut reader, &mut buf)?;
+    let coeffs = buf
+        .chunks(FQ_SIZE)
+        .map(fq_from_be_bytes)
+        .collect::<Result<Vec<Fq>, PointproofError>>()?;
+    let fq2 = |i: usize| Fq2::new(coeffs[2 * i], coeffs[2 * i + 1]);
+    let fq6 = |i: usize| Fq6::new(fq2(3 * i), fq2(3 * i + 1), fq2(3 * i + 2));
+    Ok(Fq12::new(fq6(0), fq6(1)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::param::check_parameters;
+    use crate::Commitment;
+    use crate::CommitmentScheme;
+    use crate::StructuredReferenceString;
+    use ark_ec::PairingEngine;
+    use ark_ff::BigInteger;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    fn fq_to_be_bytes(x: &Fq) -> Vec<u8> {
+        x.into_repr().to_bytes_be()
+    }
+
+    fn write_g1(p: &G1Affine, compressed: bool, buf: &mut Vec<u8>) {
+        let mut bytes = if p.is_zero() {
+            vec![0u8; if compressed { FQ_SIZE } else { 2 * FQ_SIZE }]
+        } else if compressed {
+            fq_to_be_bytes(&p.x)
+        } else {
+            [fq_to_be_bytes(&p.x), fq_to_be_bytes(&p.y)].concat()
+        };
+        if p.is_zero() {
+            bytes[0] |= INFINITY_FLAG;
+        }
+        if compressed {
+            bytes[0] |= COMPRESSION_FLAG;
+            if !p.is_zero() && p.y > -p.y {
+                bytes[0] |= SIGN_FLAG;
+            }
+        }
+        buf.extend(bytes);
+    }
+
+    fn write_g2(p: &G2Affine, compressed: bool, buf: &mut Vec<u8>) {
+        let fq2 = |x: &Fq2| [fq_to_be_bytes(&x.c1), fq_to_be_bytes(&x.c0)].concat();
+        let mut bytes = if compressed {
+            fq2(&p.x)
+        } else {
+            [fq2(&p.x), fq2(&p.y)].concat()
+        };
+        if compressed {
+            bytes[0] |= COMPRESSION_FLAG;
+            if p.y > -p.y {
+                bytes[0] |= SIGN_FLAG;
+            }
+        }
+        buf.extend(bytes);
+    }
+
+    fn write_preamble(n: usize, buf: &mut Vec<u8>) {
+        buf.push(PARAMGEN_CIPHERSUITE);
+        buf.extend((n as u32).to_be_bytes());
+    }
+
+    fn write_fq12(t: &Fq12, buf: &mut Vec<u8>) {
+        for fq6 in [t.c0, t.c1] {
+            for fq2 in [fq6.c0, fq6.c1, fq6.c2] {
+                buf.extend(fq_to_be_bytes(&fq2.c0));
+                buf.extend(fq_to_be_bytes(&fq2.c1));
+            }
+        }
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_zcash_generators() {
+        let g1 = hex("97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb");
+        assert_eq!(
+            read_g1(&g1[..]).unwrap(),
+            G1Affine::prime_subgroup_generator()
+        );
+
+        let g2 = hex("93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8");
+        assert_eq!(
+            read_g2(&g2[..]).unwrap(),
+            G2Affine::prime_subgroup_generator()
+        );
+
+        let mut infinity = [0u8; FQ_SIZE];
+        infinity[0] = COMPRESSION_FLAG | INFINITY_FLAG;
+        assert!(read_g1(&infinity[..]).unwrap().is_zero());
+    }
+
+    macro_rules! test_load_paramgen {
+        ($dim: expr, $compressed: expr) => {
+            let mut rng = test_rng();
+
+            let srs = StructuredReferenceString::<Bls12_381, $dim>::new_srs_for_testing(&mut rng)
+                .unwrap();
+            let prover_param: ProverParam<Bls12_381, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<Bls12_381, $dim> = (&srs).into();
+
+            let mut prover_bytes = Vec::new();
+            write_preamble($dim, &mut prover_bytes);
+            for g in prover_param.inner.g.iter() {
+                write_g1(g, $compressed, &mut prover_bytes);
+            }
+            let mut verifier_bytes = Vec::new();
+            write_preamble($dim, &mut verifier_bytes);
+            for h in verifier_param.inner.h.iter() {
+                write_g2(h, $compressed, &mut verifier_bytes);
+            }
+            write_fq12(&verifier_param.inner.t, &mut verifier_bytes);
+
+            let (loaded_prover_param, loaded_verifier_param) =
+                load_paramgen::<_, _, _, $dim>(&prover_bytes[..], &verifier_bytes[..], &mut rng)
+                    .unwrap();
+            assert_eq!(loaded_prover_param, prover_param);
+            assert_eq!(loaded_verifier_param, verifier_param);
+
+            let message: Vec<<Bls12_381 as PairingEngine>::Fr> = (0..$dim)
+                .map(|_| <Bls12_381 as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let commitment =
+                Commitment::<Bls12_381, $dim>::commit(&loaded_prover_param, &message).unwrap();
+            let pos = (rng.next_u32() % $dim) as usize;
+            let witness =
+                Commitment::<Bls12_381, $dim>::open(&loaded_prover_param, &message, pos).unwrap();
+            assert!(commitment
+                .verify(&loaded_verifier_param, &message[pos], pos, &witness)
+                .unwrap());
+
+            // parameters of another dimension are rejected
+            assert!(
+                ProverParam::<Bls12_381, { $dim / 2 }>::from_paramgen(&prover_bytes[..]).is_err()
+            );
+
+            // truncated parameters are rejected
+            assert!(ProverParam::<Bls12_381, $dim>::from_paramgen(
+                &prover_bytes[..prover_bytes.len() - 1]
+            )
+            .is_err());
+
+            // an unknown ciphersuite is rejected
+            let mut bad_bytes = prover_bytes.clone();
+            bad_bytes[0] = 1;
+            assert!(ProverParam::<Bls12_381, $dim>::from_paramgen(&bad_bytes[..]).is_err());
+
+            // inconsistent halves are rejected
+            let mut bad_bytes = verifier_bytes.clone();
+            let last = bad_bytes.len() - 1;
+            bad_bytes.truncate(last + 1 - 12 * FQ_SIZE);
+            write_fq12(
+                &(verifier_param.inner.t * verifier_param.inner.t),
+                &mut bad_bytes,
+            );
+            assert!(
+                load_paramgen::<_, _, _, $dim>(&prover_bytes[..], &bad_bytes[..], &mut rng)
+                    .is_err()
+            );
+        };
+    }
+
+    #[test]
+    fn test_load_paramgen() {
+        test_load_paramgen!(32, true);
+        test_load_paramgen!(32, false);
+    }
+
+    // Parameters of dimension 4 serialized with pairing-plus 0.19, the BLS12-381
+    // library of pointproofs-paramgen: zcash encoded points, compressed except
+    // for g[1] and h[1], with the point at infinity in slot n, and the GT
+    // element written by its `SerDes` implementation.
+    #[test]
+    fn test_paramgen_fixture() {
+        let mut rng = test_rng();
+        let prover_bytes = include_bytes!("testdata/paramgen_pp_n4.param");
+        let verifier_bytes = include_bytes!("testdata/paramgen_vp_n4.param");
+
+        // n is the 4 big endian bytes after the ciphersuite, in both files
+        assert_eq!(prover_bytes[..5], [PARAMGEN_CIPHERSUITE, 0, 0, 0, 4]);
+        assert_eq!(verifier_bytes[..5], prover_bytes[..5]);
+        // slot n follows g[0], the uncompressed g[1], g[2] and g[3]
+        let slot = 5 + FQ_SIZE + 2 * FQ_SIZE + 2 * FQ_SIZE;
+        assert_eq!(prover_bytes[slot], COMPRESSION_FLAG | INFINITY_FLAG);
+
+        let (prover_param, verifier_param) =
+            load_paramgen::<_, _, _, 4>(&prover_bytes[..], &verifier_bytes[..], &mut rng).unwrap();
+        check_parameters(&prover_param, &verifier_param, &mut rng).unwrap();
+
+        let message: Vec<<Bls12_381 as PairingEngine>::Fr> = (0..4)
+            .map(|_| <Bls12_381 as PairingEngine>::Fr::rand(&mut rng))
+            .collect();
+        let commitment = Commitment::<Bls12_381, 4>::commit(&prover_param, &message).unwrap();
+        for (pos, input) in message.iter().enumerate() {
+            let witness = Commitment::<Bls12_381, 4>::open(&prover_param, &message, pos).unwrap();
+            assert!(commitment
+                .verify(&verifier_param, input, pos, &witness)
+                .unwrap());
+        }
+    }
+}
@@ -0,0 +1,427 @@
+use crate::ceremony::Contribution;
+use crate::error::check_dimension;
+use crate::error::malformed;
+use crate::param::check_dyn_parameters;
+use crate::param::DynProverParam;
+use crate::param::DynStructuredReferenceString;
+use crate::param::DynVerifierParam;
+use crate::PointproofError;
+use ark_bn254::Bn254;
+use ark_bn254::Fq;
+use ark_bn254::Fq2;
+use ark_bn254::FqParameters;
+use ark_bn254::G1Affine;
+use ark_bn254::G2Affine;
+use ark_ec::AffineCurve;
+use ark_ec::PairingEngine;
+use ark_ff::BigInteger256;
+use ark_ff::FpParameters;
+use ark_std::rand::Rng;
+use ark_std::Zero;
+use std::convert::TryInto;
+use std::io::Read;
+
+// A .ptau file, as written by snarkjs for the Perpetual Powers of Tau ceremony, is
+//     "ptau" || version (u32) || number of sections (u32) || sections
+// where each section is
+//     section type (u32) || section size (u64) || section data
+// and all integers are little endian. The sections used here are
+// - 1: header, n8 (u32) || q (n8 bytes) || power (u32) || ...
+// - 2: tau^i * G for i in 0..2^{power+1} - 1
+// - 3: tau^i * H for i in 0..2^power
+// The remaining sections (contributions, Lagrange bases, ...) are skipped.
+//
+// Points are uncompressed affine coordinates, and every Fq element is stored
+// as n8 = 32 little endian bytes in Montgomery form, with R = 2^256 as in
+// `ark_bn254::Fq`. An Fq2 element is c0 || c1, and the point at infinity is
+// all zeros.
+
+const PTAU_MAGIC: &[u8; 4] = b"ptau";
+const HEADER_SECTION: u32 = 1;
+const TAU_G1_SECTION: u32 = 2;
+const TAU_G2_SECTION: u32 = 3;
+
+/// Size of a little endian Fq element
+const FQ_SIZE: usize = 32;
+const G1_SIZE: usize = 2 * FQ_SIZE;
+const G2_SIZE: usize = 4 * FQ_SIZE;
+
+/// The powers of a .ptau file, as the starting point of a ceremony.
+///
+/// A .ptau file publishes tau^{n+1} * G, and commitments are only binding while
+/// alpha^{n+1} * G is unknown: with it, a witness w of an input m turns into
+/// w - delta * tau^{n+1} * G, which verifies for m + delta. So the powers are not
+/// an SRS on their own; `contribute` re-randomizes them into an SRS with the
+/// trapdoor tau * beta, which is binding as long as beta is kept secret.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PtauStart<E: PairingEngine> {
+    srs: DynStructuredReferenceString<E>,
+}
+
+impl PtauStart<Bn254> {
+    /// Read the powers for dimension `n` from a .ptau file of the Perpetual Powers of
+    /// Tau ceremony, with alpha set to the ceremony's tau:
+    /// - g0 = tau^0 * G, and g = tau^1 * G ... tau^{2n} * G with tau^{n+1} * G dropped
+    /// - h = tau^1 * H ... tau^n * H
+    /// - t = e(tau^{n+1} * G, H)
+    ///
+    /// The file must hold at least 2n + 1 powers in G1 and n + 1 powers in G2,
+    /// i.e., its power must satisfy 2^power > n.
+    /// The powers are checked with `check_dyn_parameters`.
+    pub fn from_ptau<P: Read, R: Rng>(
+        mut reader: P,
+        n: usize,
+        rng: &mut R,
+    ) -> Result<Self, PointproofError> {
+        if n == 0 {
+            return Err(malformed("the dimension must be positive"));
+        }
+
+        let mut buf = [0u8; 12];
+        read_bytes(&mut reader, &mut buf)?;
+        if &buf[..4] != PTAU_MAGIC {
+            return Err(malformed("not a ptau file"));
+        }
+        let num_sections = u32::from_le_bytes(buf[8..12].try_into().unwrap());
+
+        let mut has_header = false;
+        let mut tau_g1 = None;
+        let mut tau_g2 = None;
+        for _ in 0..num_sections {
+            read_bytes(&mut reader, &mut buf)?;
+            let section = u32::from_le_bytes(buf[..4].try_into().unwrap());
+            let size = u64::from_le_bytes(buf[4..12].try_into().unwrap());
+            let mut section_reader = (&mut reader).take(size);
+
+            match section {
+                HEADER_SECTION if !has_header => {
+                    read_header(&mut section_reader)?;
+                    has_header = true;
+                }
+                TAU_G1_SECTION if tau_g1.is_none() => {
+                    // tau^0 ... tau^{2n}
+                    let points =
+                        read_points(&mut section_reader, size, G1_SIZE, 2 * n + 1, read_g1)?;
+                    tau_g1 = Some(points);
+                }
+                TAU_G2_SECTION if tau_g2.is_none() => {
+                    // tau^0 ... tau^n
+                    let points = read_points(&mut section_reader, size, G2_SIZE, n + 1, read_g2)?;
+                    tau_g2 = Some(points);
+                }
+                HEADER_SECTION | TAU_G1_SECTION | TAU_G2_SECTION => {
+                    return Err(malformed("duplicated section"));
+                }
+                _ => (),
+            }
+            skip(&mut section_reader)?;
+        }
+
+        let (mut tau_g1, tau_g2) = match (has_header, tau_g1, tau_g2) {
+            (true, Some(tau_g1), Some(tau_g2)) => (tau_g1, tau_g2),
+            _ => return Err(malformed("missing sections")),
+        };
+        if tau_g1[0] != G1Affine::prime_subgroup_generator()
+            || tau_g2[0] != G2Affine::prime_subgroup_generator()
+        {
+            return Err(malformed("the powers do not start from the generators"));
+        }
+
+        let t = Bn254::pairing(tau_g1[n + 1], tau_g2[0]);
+        tau_g1[n + 1] = G1Affine::zero();
+        let srs = DynStructuredReferenceString {
+            g0: tau_g1[0],
+            g: tau_g1[1..].to_vec(),
+            h: tau_g2[1..].to_vec(),
+            t,
+        };
+
+        let prover_param: DynProverParam<Bn254> = (&srs).into();
+        let verifier_param: DynVerifierParam<Bn254> = (&srs).into();
+        check_dyn_parameters(&prover_param, &verifier_param, rng)?;
+        Ok(Self { srs })
+    }
+}
+
+impl<E: PairingEngine> PtauStart<E> {
+    /// The dimension of the SRS built from the powers
+    pub fn dimension(&self) -> usize {
+        self.srs.dimension()
+    }
+
+    /// Re-randomize the powers with a secret beta sampled from `rng`, and return
+    /// the resulting SRS with the proof of the update. Further contributions are
+    /// made with `DynStructuredReferenceString::contribute`.
+    pub fn contribute<R: Rng>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(DynStructuredReferenceString<E>, Contribution<E>), PointproofError> {
+        let mut srs = self.srs.clone();
+        let contribution = srs.contribute(rng)?;
+        Ok((srs, contribution))
+    }
+
+    /// Verify that `srs` is the result of the contributions, in order, starting
+    /// from these powers, and that `srs` is a valid SRS.
+    pub fn verify_ceremony<R: Rng>(
+        &self,
+        srs: &DynStructuredReferenceString<E>,
+        contributions: &[Contribution<E>],
+        rng: &mut R,
+    ) -> Result<(), PointproofError> {
+        check_dimension(self.dimension(), srs.dimension())?;
+        srs.verify_contributions(self.srs.g[0], contributions, rng)
+    }
+}
+
+/// Check that the file is over the BN254 base field
+fn read_header<R: Read>(mut reader: R) -> Result<(), PointproofError> {
+    let mut n8 = [0u8; 4];
+    read_bytes(&mut reader, &mut n8)?;
+    if u32::from_le_bytes(n8) as usize != FQ_SIZE {
+        return Err(malformed("unsupported field size"));
+    }
+    let mut q = [0u8; FQ_SIZE];
+    read_bytes(&mut reader, &mut q)?;
+    if bigint_from_le_bytes(&q) != FqParameters::MODULUS {
+        return Err(malformed("not a BN254 ptau file"));
+    }
+    Ok(())
+}
+
+/// Read the first `num` points of a section of `size` bytes,
+/// or fail if the section holds fewer than `num` points
+fn read_points<R: Read, P>(
+    mut reader: R,
+    size: u64,
+    point_size: usize,
+    num: usize,
+    read_point: fn(&[u8]) -> Result<P, PointproofError>,
+) -> Result<Vec<P>, PointproofError> {
+    if !size.is_multiple_of(point_size as u64) {
+        return Err(malformed("invalid section size"));
+    }
+    let num_powers = size / point_size as u64;
+    if num_powers < num as u64 {
+        return Err(PointproofError::DegreeTooLarge {
+            degree: num - 1,
+            num_powers: num_powers as usize,
+        });
+    }
+
+    let mut buf = vec![0u8; point_size];
+    (0..num)
+        .map(|_| {
+            read_bytes(&mut reader, &mut buf)?;
+            read_point(&buf)
+        })
+        .collect()
+}
+
+fn read_bytes<R: Read>(mut reader: R, buf: &mut [u8]) -> Result<(), PointproofError> {
+    reader
+        .read_exact(buf)
+        .map_err(|_| malformed("the file is truncated"))
+}
+
+/// Consume the rest of a section
+fn skip<R: Read>(reader: &mut std::io::Take<R>) -> Result<(), PointproofError> {
+    let remaining = reader.limit();
+    match std::io::copy(reader, &mut std::io::sink()) {
+        Ok(skipped) if skipped == remaining => Ok(()),
+        _ => Err(malformed("the file is truncated")),
+    }
+}
+
+fn bigint_from_le_bytes(bytes: &[u8]) -> BigInteger256 {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    BigInteger256(limbs)
+}
+
+/// Parse a little endian Fq element in Montgomery form
+fn fq_from_le_bytes(bytes: &[u8]) -> Result<Fq, PointproofError> {
+    let repr = bigint_from_le_bytes(bytes);
+    if repr >= FqParameters::MODULUS {
+        return Err(malformed("non-canonical field element"));
+    }
+    Ok(Fq::new(repr))
+}
+
+fn read_g1(bytes: &[u8]) -> Result<G1Affine, PointproofError> {
+    if bytes.iter().all(|&b| b == 0) {
+        return Ok(G1Affine::zero());
+    }
+    let x = fq_from_le_bytes(&bytes[..FQ_SIZE])?;
+    let y = fq_from_le_bytes(&bytes[FQ_SIZE..])?;
+    let point = G1Affine::new(x, y, false);
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(malformed("invalid G1 point"));
+    }
+    Ok(point)
+}
+
+fn read_g2(bytes: &[u8]) -> Result<G2Affine, PointproofError> {
+    if bytes.iter().all(|&b| b == 0) {
+        return Ok(G2Affine::zero());
+    }
+    let fq2 = |bytes: &[u8]| -> Result<Fq2, PointproofError> {
+        Ok(Fq2::new(
+            fq_from_le_bytes(&bytes[..FQ_SIZE])?,
+            fq_from_le_bytes(&bytes[FQ_SIZE..])?,
+        ))
+    };
+    let x = fq2(&bytes[..2 * FQ_SIZE])?;
+    let y = fq2(&bytes[2 * FQ_SIZE..])?;
+    let point = G2Affine::new(x, y, false);
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(malformed("invalid G2 point"));
+    }
+    Ok(point)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::param::ProverParam;
+    use crate::param::StructuredReferenceString;
+    use crate::param::VerifierParam;
+    use crate::Commitment;
+    use crate::CommitmentScheme;
+    use ark_bn254::Fr;
+    use ark_ec::ProjectiveCurve;
+    use ark_ff::BigInteger;
+    use ark_ff::Field;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    fn write_fq(x: &Fq, buf: &mut Vec<u8>) {
+        // the internal representation of Fq is in Montgomery form
+        buf.extend(x.0.to_bytes_le());
+    }
+
+    fn write_section(section: u32, data: &[u8], buf: &mut Vec<u8>) {
+        buf.extend(section.to_le_bytes());
+        buf.extend((data.len() as u64).to_le_bytes());
+        buf.extend(data);
+    }
+
+    /// A ptau file of the given power with trapdoor tau
+    fn write_ptau(tau: Fr, power: u32) -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend((FQ_SIZE as u32).to_le_bytes());
+        header.extend(FqParameters::MODULUS.to_bytes_le());
+        header.extend(power.to_le_bytes());
+        header.extend(power.to_le_bytes());
+
+        let mut tau_g1 = Vec::new();
+        for i in 0..(2u64 << power) - 1 {
+            let p = G1Affine::prime_subgroup_generator()
+                .mul(tau.pow([i]))
+                .into_affine();
+            write_fq(&p.x, &mut tau_g1);
+            write_fq(&p.y, &mut tau_g1);
+        }
+
+        let mut tau_g2 = Vec::new();
+        for i in 0..1u64 << power {
+            let p = G2Affine::prime_subgroup_generator()
+                .mul(tau.pow([i]))
+                .into_affine();
+            for c in [p.x.c0, p.x.c1, p.y.c0, p.y.c1] {
+                write_fq(&c, &mut tau_g2);
+            }
+        }
+
+        let mut buf = PTAU_MAGIC.to_vec();
+        buf.extend(1u32.to_le_bytes());
+        buf.extend(4u32.to_le_bytes());
+        write_section(HEADER_SECTION, &header, &mut buf);
+        write_section(TAU_G1_SECTION, &tau_g1, &mut buf);
+        write_section(TAU_G2_SECTION, &tau_g2, &mut buf);
+        // sections that are not needed are skipped
+        write_section(4, &[0u8; 100], &mut buf);
+        buf
+    }
+
+    macro_rules! test_from_ptau {
+        ($dim: expr, $power: expr) => {
+            let mut rng = test_rng();
+
+            let tau = Fr::rand(&mut rng);
+            let ptau = write_ptau(tau, $power);
+            let start = PtauStart::<Bn254>::from_ptau(&ptau[..], $dim, &mut rng).unwrap();
+            assert_eq!(start.dimension(), $dim);
+            assert_eq!(
+                start.srs.g[0],
+                G1Affine::prime_subgroup_generator().mul(tau).into_affine()
+            );
+            assert!(start.srs.g[$dim].is_zero());
+            assert_eq!(
+                start.srs.h[$dim - 1],
+                G2Affine::prime_subgroup_generator()
+                    .mul(tau.pow([$dim as u64]))
+                    .into_affine()
+            );
+
+            let (srs, contribution) = start.contribute(&mut rng).unwrap();
+            let contributions = [contribution];
+            start
+                .verify_ceremony(&srs, &contributions, &mut rng)
+                .unwrap();
+            assert!(start.verify_ceremony(&srs, &[], &mut rng).is_err());
+            assert!(start
+                .verify_ceremony(&start.srs, &contributions, &mut rng)
+                .is_err());
+
+            let srs = StructuredReferenceString::<Bn254, $dim>::try_from(srs).unwrap();
+            let prover_param: ProverParam<Bn254, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<Bn254, $dim> = (&srs).into();
+            let message: Vec<Fr> = (0..$dim).map(|_| Fr::rand(&mut rng)).collect();
+            let commitment = Commitment::<Bn254, $dim>::commit(&prover_param, &message).unwrap();
+            let pos = (rng.next_u32() % $dim) as usize;
+            let witness = Commitment::<Bn254, $dim>::open(&prover_param, &message, pos).unwrap();
+            assert!(commitment
+                .verify(&verifier_param, &message[pos], pos, &witness)
+                .unwrap());
+
+            // the published tau^{n+1} * G no longer forges openings
+            let tau_n1_g = G1Affine::prime_subgroup_generator().mul(tau.pow([$dim as u64 + 1]));
+            assert!(!commitment
+                .verify(
+                    &verifier_param,
+                    &(message[pos] + Fr::from(1u64)),
+                    pos,
+                    &(witness - tau_n1_g)
+                )
+                .unwrap());
+
+            // a file with too few powers is rejected
+            let small_ptau = write_ptau(tau, $power - 1);
+            assert!(matches!(
+                PtauStart::<Bn254>::from_ptau(&small_ptau[..], $dim, &mut rng),
+                Err(PointproofError::DegreeTooLarge { .. })
+            ));
+
+            // truncated files are rejected
+            assert!(
+                PtauStart::<Bn254>::from_ptau(&ptau[..ptau.len() - 1], $dim, &mut rng).is_err()
+            );
+
+            // a point that is not on the curve is rejected
+            let mut bad_ptau = ptau.clone();
+            let pos = 12 + 12 + 44 + 12 + G1_SIZE;
+            bad_ptau[pos] ^= 1;
+            assert!(PtauStart::<Bn254>::from_ptau(&bad_ptau[..], $dim, &mut rng).is_err());
+        };
+    }
+
+    #[test]
+    fn test_from_ptau() {
+        // 2^6 = 64 > 63
+        test_from_ptau!(63, 6);
+    }
+}
@@ -0,0 +1,651 @@
+use crate::param::DynProverParam;
+use crate::param::DynStructuredReferenceString;
+use crate::param::DynVerifierParam;
+use crate::param::ProverParam;
+use crate::param::StructuredReferenceString;
+use crate::param::VerifierParam;
+use crate::state::DynProverState;
+use crate::state::ProverState;
+use crate::Commitment;
+use crate::DynCommitment;
+use ark_ec::AffineCurve;
+use ark_ec::PairingEngine;
+use ark_serialize::CanonicalDeserialize;
+use ark_serialize::CanonicalSerialize;
+use ark_serialize::Read;
+use ark_serialize::SerializationError;
+use ark_serialize::Write;
+use ark_std::Zero;
+use sha2::Digest;
+use sha2::Sha512;
+use std::collections::BTreeMap;
+
+/// Magic bytes at the beginning of every serialized parameter
+const MAGIC: &[u8; 4] = b"PPRF";
+
+/// Version of the parameter serialization format
+const VERSION: u8 = 1;
+
+/// Size of the header of a serialized parameter:
+/// magic (4 bytes) || version (1 byte) || kind (1 byte) || compressed (1 byte)
+///     || curve fingerprint (8 bytes) || n (8 bytes, little endian)
+const HEADER_SIZE: usize = 23;
+
+/// The kind of parameter recorded in the header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParamKind {
+    StructuredReferenceString = 0,
+    ProverParam = 1,
+    VerifierParam = 2,
+}
+
+/// How the group elements are (de)serialized
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Compressed,
+    Uncompressed,
+    Unchecked,
+}
+
+/// The curve fingerprint is the first 8 bytes of
+/// SHA-512(G1 generator || G2 generator), with both generators uncompressed.
+fn curve_fingerprint<E: PairingEngine>() -> [u8; 8] {
+    let mut buf = Vec::new();
+    E::G1Affine::prime_subgroup_generator()
+        .serialize_uncompressed(&mut buf)
+        .expect("serializing into a vector never fails");
+    E::G2Affine::prime_subgroup_generator()
+        .serialize_uncompressed(&mut buf)
+        .expect("serializing into a vector never fails");
+    let digest = Sha512::digest(&buf);
+    let mut res = [0u8; 8];
+    res.copy_from_slice(&digest[..8]);
+    res
+}
+
+fn write_header<E: PairingEngine, W: Write>(
+    mut writer: W,
+    kind: ParamKind,
+    compressed: bool,
+    n: usize,
+) -> Result<(), SerializationError> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[VERSION, kind as u8, compressed as u8])?;
+    writer.write_all(&curve_fingerprint::<E>())?;
+    writer.write_all(&(n as u64).to_le_bytes())?;
+    Ok(())
+}
+
+/// Read a header and reject it unless it matches the expected parameter.
+/// Returns the dimension recorded in the header.
+fn read_header<E: PairingEngine, R: Read>(
+    mut reader: R,
+    kind: ParamKind,
+    compressed: bool,
+) -> Result<usize, SerializationError> {
+    let mut header = [0u8; HEADER_SIZE];
+    reader.read_exact(&mut header)?;
+
+    let mut expected = Vec::with_capacity(HEADER_SIZE);
+    write_header::<E, _>(&mut expected, kind, compressed, 0)?;
+    if header[..HEADER_SIZE - 8] != expected[..HEADER_SIZE - 8] {
+        return Err(SerializationError::InvalidData);
+    }
+    read_dimension(&header[HEADER_SIZE - 8..])
+}
+
+/// Parse a little endian dimension, rejecting 0 and the dimensions
+/// whose 2n G1 powers cannot be indexed
+fn read_dimension(bytes: &[u8]) -> Result<usize, SerializationError> {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(bytes);
+    match usize::try_from(u64::from_le_bytes(buf)) {
+        Ok(n) if n != 0 && n <= usize::MAX >> 1 => Ok(n),
+        _ => Err(SerializationError::InvalidData),
+    }
+}
+
+fn write_element<T: CanonicalSerialize, W: Write>(
+    element: &T,
+    writer: W,
+    compressed: bool,
+) -> Result<(), SerializationError> {
+    if compressed {
+        element.serialize(writer)
+    } else {
+        element.serialize_uncompressed(writer)
+    }
+}
+
+fn element_size<T: CanonicalSerialize>(element: &T, compressed: bool) -> usize {
+    if compressed {
+        element.serialized_size()
+    } else {
+        element.uncompressed_size()
+    }
+}
+
+fn read_element<T: CanonicalDeserialize, R: Read>(
+    reader: R,
+    mode: Mode,
+) -> Result<T, SerializationError> {
+    match mode {
+        Mode::Compressed => T::deserialize(reader),
+        Mode::Uncompressed => T::deserialize_uncompressed(reader),
+        Mode::Unchecked => T::deserialize_unchecked(reader),
+    }
+}
+
+fn read_elements<T: CanonicalDeserialize, R: Read>(
+    mut reader: R,
+    len: usize,
+    mode: Mode,
+) -> Result<Vec<T>, SerializationError> {
+    (0..len).map(|_| read_element(&mut reader, mode)).collect()
+}
+
+/// The G1 powers, with the empty slot for alpha^{n+1} stored as the point at infinity
+fn read_g<E: PairingEngine, R: Read>(
+    reader: R,
+    n: usize,
+    mode: Mode,
+) -> Result<Vec<E::G1Affine>, SerializationError> {
+    let g: Vec<E::G1Affine> = read_elements(reader, n << 1, mode)?;
+    if !g[n].is_zero() {
+        return Err(SerializationError::InvalidData);
+    }
+    Ok(g)
+}
+
+impl<E: PairingEngine> DynStructuredReferenceString<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compressed: bool,
+    ) -> Result<(), SerializationError> {
+        write_header::<E, _>(
+            &mut writer,
+            ParamKind::StructuredReferenceString,
+            compressed,
+            self.dimension(),
+        )?;
+        write_element(&self.g0, &mut writer, compressed)?;
+        for g in self.g.iter() {
+            write_element(g, &mut writer, compressed)?;
+        }
+        for h in self.h.iter() {
+            write_element(h, &mut writer, compressed)?;
+        }
+        write_element(&self.t, &mut writer, compressed)
+    }
+
+    fn size_with_mode(&self, compressed: bool) -> usize {
+        HEADER_SIZE
+            + element_size(&self.g0, compressed)
+            + self
+                .g
+                .iter()
+                .map(|g| element_size(g, compressed))
+                .sum::<usize>()
+            + self
+                .h
+                .iter()
+                .map(|h| element_size(h, compressed))
+                .sum::<usize>()
+            + element_size(&self.t, compressed)
+    }
+
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        mode: Mode,
+    ) -> Result<Self, SerializationError> {
+        let n = read_header::<E, _>(
+            &mut reader,
+            ParamKind::StructuredReferenceString,
+            mode == Mode::Compressed,
+        )?;
+        let g0 = read_element(&mut reader, mode)?;
+        let g = read_g::<E, _>(&mut reader, n, mode)?;
+        let h = read_elements(&mut reader, n, mode)?;
+        let t = read_element(&mut reader, mode)?;
+        Ok(Self { g0, g, h, t })
+    }
+}
+
+impl<E: PairingEngine> DynProverParam<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compressed: bool,
+    ) -> Result<(), SerializationError> {
+        write_header::<E, _>(
+            &mut writer,
+            ParamKind::ProverParam,
+            compressed,
+            self.dimension(),
+        )?;
+        write_element(&self.g0, &mut writer, compressed)?;
+        for g in self.g.iter() {
+            write_element(g, &mut writer, compressed)?;
+        }
+        Ok(())
+    }
+
+    fn size_with_mode(&self, compressed: bool) -> usize {
+        HEADER_SIZE
+            + element_size(&self.g0, compressed)
+            + self
+                .g
+                .iter()
+                .map(|g| element_size(g, compressed))
+                .sum::<usize>()
+    }
+
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        mode: Mode,
+    ) -> Result<Self, SerializationError> {
+        let n = read_header::<E, _>(
+            &mut reader,
+            ParamKind::ProverParam,
+            mode == Mode::Compressed,
+        )?;
+        let g0 = read_element(&mut reader, mode)?;
+        let g = read_g::<E, _>(&mut reader, n, mode)?;
+        Ok(Self { g0, g })
+    }
+}
+
+impl<E: PairingEngine> DynVerifierParam<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compressed: bool,
+    ) -> Result<(), SerializationError> {
+        write_header::<E, _>(
+            &mut writer,
+            ParamKind::VerifierParam,
+            compressed,
+            self.dimension(),
+        )?;
+        for h in self.h.iter() {
+            write_element(h, &mut writer, compressed)?;
+        }
+        write_element(&self.t, &mut writer, compressed)
+    }
+
+    fn size_with_mode(&self, compressed: bool) -> usize {
+        HEADER_SIZE
+            + self
+                .h
+                .iter()
+                .map(|h| element_size(h, compressed))
+                .sum::<usize>()
+            + element_size(&self.t, compressed)
+    }
+
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        mode: Mode,
+    ) -> Result<Self, SerializationError> {
+        let n = read_header::<E, _>(
+            &mut reader,
+            ParamKind::VerifierParam,
+            mode == Mode::Compressed,
+        )?;
+        let h = read_elements(&mut reader, n, mode)?;
+        let t = read_element(&mut reader, mode)?;
+        Ok(Self { h, t })
+    }
+}
+
+macro_rules! impl_canonical_serialization {
+    ($param: ident) => {
+        impl<E: PairingEngine> CanonicalSerialize for $param<E> {
+            fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+                self.serialize_with_mode(writer, true)
+            }
+
+            fn serialized_size(&self) -> usize {
+                self.size_with_mode(true)
+            }
+
+            fn serialize_uncompressed<W: Write>(
+                &self,
+                writer: W,
+            ) -> Result<(), SerializationError> {
+                self.serialize_with_mode(writer, false)
+            }
+
+            fn uncompressed_size(&self) -> usize {
+                self.size_with_mode(false)
+            }
+        }
+
+        impl<E: PairingEngine> CanonicalDeserialize for $param<E> {
+            fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+                Self::deserialize_with_mode(reader, Mode::Compressed)
+            }
+
+            fn deserialize_uncompressed<R: Read>(reader: R) -> Result<Self, SerializationError> {
+                Self::deserialize_with_mode(reader, Mode::Uncompressed)
+            }
+
+            fn deserialize_unchecked<R: Read>(reader: R) -> Result<Self, SerializationError> {
+                Self::deserialize_with_mode(reader, Mode::Unchecked)
+            }
+        }
+    };
+}
+
+impl_canonical_serialization!(DynStructuredReferenceString);
+impl_canonical_serialization!(DynProverParam);
+impl_canonical_serialization!(DynVerifierParam);
+
+// The parameters of dimension N are serialized as their runtime counterparts,
+// and rejected if the dimension in the header is not N.
+macro_rules! impl_canonical_serialization_for_dimension {
+    ($param: ident, $dyn_param: ident) => {
+        impl<E: PairingEngine, const N: usize> CanonicalSerialize for $param<E, N> {
+            fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+                self.inner.serialize(writer)
+            }
+
+            fn serialized_size(&self) -> usize {
+                self.inner.serialized_size()
+            }
+
+            fn serialize_uncompressed<W: Write>(
+                &self,
+                writer: W,
+            ) -> Result<(), SerializationError> {
+                self.inner.serialize_uncompressed(writer)
+            }
+
+            fn uncompressed_size(&self) -> usize {
+                self.inner.uncompressed_size()
+            }
+        }
+
+        impl<E: PairingEngine, const N: usize> CanonicalDeserialize for $param<E, N> {
+            fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+                $dyn_param::deserialize(reader)?
+                    .try_into()
+                    .map_err(|_| SerializationError::InvalidData)
+            }
+
+            fn deserialize_uncompressed<R: Read>(reader: R) -> Result<Self, SerializationError> {
+                $dyn_param::deserialize_uncompressed(reader)?
+                    .try_into()
+                    .map_err(|_| SerializationError::InvalidData)
+            }
+
+            fn deserialize_unchecked<R: Read>(reader: R) -> Result<Self, SerializationError> {
+                $dyn_param::deserialize_unchecked(reader)?
+                    .try_into()
+                    .map_err(|_| SerializationError::InvalidData)
+            }
+        }
+    };
+}
+
+impl_canonical_serialization_for_dimension!(
+    StructuredReferenceString,
+    DynStructuredReferenceString
+);
+impl_canonical_serialization_for_dimension!(ProverParam, DynProverParam);
+impl_canonical_serialization_for_dimension!(VerifierParam, DynVerifierParam);
+
+// A commitment of dimension N is serialized as a single G1 point, the same way
+// as a witness. A commitment of a runtime dimension is prefixed with its
+// dimension (8 bytes, little endian).
+impl<E: PairingEngine, const N: usize> CanonicalSerialize for Commitment<E, N> {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.inner.commitment.serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.inner.commitment.serialized_size()
+    }
+
+    fn serialize_uncompressed<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.inner.commitment.serialize_uncompressed(writer)
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        self.inner.commitment.uncompressed_size()
+    }
+}
+
+impl<E: PairingEngine, const N: usize> CanonicalDeserialize for Commitment<E, N> {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Ok(Self::from_point(E::G1Projective::deserialize(reader)?))
+    }
+
+    fn deserialize_uncompressed<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Ok(Self::from_point(E::G1Projective::deserialize_uncompressed(
+            reader,
+        )?))
+    }
+
+    fn deserialize_unchecked<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Ok(Self::from_point(E::G1Projective::deserialize_unchecked(
+            reader,
+        )?))
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Commitment<E, N> {
+    fn from_point(commitment: E::G1Projective) -> Self {
+        Self {
+            inner: DynCommitment { n: N, commitment },
+        }
+    }
+}
+
+impl<E: PairingEngine> CanonicalSerialize for DynCommitment<E> {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        writer.write_all(&(self.n as u64).to_le_bytes())?;
+        self.commitment.serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        8 + self.commitment.serialized_size()
+    }
+
+    fn serialize_uncompressed<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        writer.write_all(&(self.n as u64).to_le_bytes())?;
+        self.commitment.serialize_uncompressed(writer)
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        8 + self.commitment.uncompressed_size()
+    }
+}
+
+impl<E: PairingEngine> CanonicalDeserialize for DynCommitment<E> {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Self::deserialize_with_mode(reader, Mode::Compressed)
+    }
+
+    fn deserialize_uncompressed<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Self::deserialize_with_mode(reader, Mode::Uncompressed)
+    }
+
+    fn deserialize_unchecked<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Self::deserialize_with_mode(reader, Mode::Unchecked)
+    }
+}
+
+impl<E: PairingEngine> DynCommitment<E> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        mode: Mode,
+    ) -> Result<Self, SerializationError> {
+        let mut buf = [0u8; 8];
+        reader.read_exact(&mut buf)?;
+        let n = read_dimension(&buf)?;
+        let commitment = read_element(&mut reader, mode)?;
+        Ok(Self { n, commitment })
+    }
+}
+
+// A prover state is serialized as
+//     commitment (with its dimension n) || n inputs
+//         || number of cached witnesses (8 bytes, little endian)
+//         || (position (8 bytes, little endian) || witness) for each cached witness
+// with the positions in increasing order.
+impl<E: PairingEngine> DynProverState<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compressed: bool,
+    ) -> Result<(), SerializationError> {
+        write_element(&self.commitment, &mut writer, compressed)?;
+        for input in self.inputs.iter() {
+            write_element(input, &mut writer, compressed)?;
+        }
+        writer.write_all(&(self.witnesses.len() as u64).to_le_bytes())?;
+        for (pos, witness) in self.witnesses.iter() {
+            writer.write_all(&(*pos as u64).to_le_bytes())?;
+            write_element(witness, &mut writer, compressed)?;
+        }
+        Ok(())
+    }
+
+    fn size_with_mode(&self, compressed: bool) -> usize {
+        element_size(&self.commitment, compressed)
+            + self
+                .inputs
+                .iter()
+                .map(|input| element_size(input, compressed))
+                .sum::<usize>()
+            + 8
+            + self
+                .witnesses
+                .values()
+                .map(|witness| 8 + element_size(witness, compressed))
+                .sum::<usize>()
+    }
+
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        mode: Mode,
+    ) -> Result<Self, SerializationError> {
+        let commitment = DynCommitment::<E>::deserialize_with_mode(&mut reader, mode)?;
+        let n = commitment.n;
+        let inputs = read_elements(&mut reader, n, mode)?;
+
+        let num_witnesses = read_u64(&mut reader)?;
+        if num_witnesses > n as u64 {
+            return Err(SerializationError::InvalidData);
+        }
+        let mut witnesses = BTreeMap::new();
+        for _ in 0..num_witnesses {
+            let pos = read_u64(&mut reader)?;
+            // the positions are in range and strictly increasing
+            if pos >= n as u64
+                || witnesses
+                    .keys()
+                    .next_back()
+                    .is_some_and(|&last| pos <= last as u64)
+            {
+                return Err(SerializationError::InvalidData);
+            }
+            witnesses.insert(pos as usize, read_element(&mut reader, mode)?);
+        }
+        Ok(Self {
+            inputs,
+            commitment,
+            witnesses,
+        })
+    }
+}
+
+fn read_u64<R: Read>(mut reader: R) -> Result<u64, SerializationError> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+impl_canonical_serialization!(DynProverState);
+impl_canonical_serialization_for_dimension!(ProverState, DynProverState);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CommitmentScheme;
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    macro_rules! test_serialization {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs = StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            let message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let commitment = Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap();
+            let pos = (rng.next_u32() % $dim) as usize;
+            let witness = Commitment::<$engine, $dim>::open(&prover_param, &message, pos).unwrap();
+
+            macro_rules! round_trip {
+                ($value: expr, $ty: ty) => {
+                    let mut buf = Vec::new();
+                    $value.serialize(&mut buf).unwrap();
+                    assert_eq!(buf.len(), $value.serialized_size());
+                    assert_eq!(<$ty>::deserialize(&buf[..]).unwrap(), $value);
+                    // the compressed form is rejected as uncompressed
+                    assert!(<$ty>::deserialize_uncompressed(&buf[..]).is_err());
+
+                    let mut buf = Vec::new();
+                    $value.serialize_uncompressed(&mut buf).unwrap();
+                    assert_eq!(buf.len(), $value.uncompressed_size());
+                    assert_eq!(<$ty>::deserialize_uncompressed(&buf[..]).unwrap(), $value);
+                    assert_eq!(<$ty>::deserialize_unchecked(&buf[..]).unwrap(), $value);
+                };
+            }
+            round_trip!(srs, StructuredReferenceString<$engine, $dim>);
+            round_trip!(prover_param, ProverParam<$engine, $dim>);
+            round_trip!(verifier_param, VerifierParam<$engine, $dim>);
+            round_trip!(commitment, Commitment<$engine, $dim>);
+
+            let mut buf = Vec::new();
+            witness.serialize(&mut buf).unwrap();
+            let witness =
+                <$engine as PairingEngine>::G1Projective::deserialize(&buf[..]).unwrap();
+            assert!(commitment
+                .verify(&verifier_param, &message[pos], pos, &witness)
+                .unwrap());
+
+            // parameters of a different size, curve or kind are rejected
+            let mut buf = Vec::new();
+            prover_param.serialize(&mut buf).unwrap();
+            assert!(ProverParam::<$engine, { $dim / 2 }>::deserialize(&buf[..]).is_err());
+            assert!(ProverParam::<Bls12_381, $dim>::deserialize(&buf[..]).is_err());
+            assert!(StructuredReferenceString::<$engine, $dim>::deserialize(&buf[..]).is_err());
+
+            // truncated parameters are rejected
+            assert!(ProverParam::<$engine, $dim>::deserialize(&buf[..buf.len() - 1]).is_err());
+
+            // parameters of a runtime dimension share the format of the typed ones
+            let dyn_prover_param = DynProverParam::<$engine>::deserialize(&buf[..]).unwrap();
+            assert_eq!(dyn_prover_param.dimension(), $dim);
+            let dyn_commitment = DynCommitment::from(commitment);
+            round_trip!(dyn_prover_param, DynProverParam<$engine>);
+            round_trip!(dyn_commitment, DynCommitment<$engine>);
+        };
+    }
+
+    #[test]
+    fn test_serialization() {
+        test_serialization!(Bn254, 128, "bn254");
+    }
+}
@@ -0,0 +1,183 @@
+use crate::error::check_position;
+use crate::param::DynProverParam;
+use crate::param::ProverParam;
+use crate::Commitment;
+use crate::DynCommitment;
+use crate::PointproofError;
+use ark_ec::msm::VariableBaseMSM;
+use ark_ec::PairingEngine;
+use ark_ff::PrimeField;
+use ark_std::Zero;
+
+// A sparse vector is given by its (position, input) entries, where the positions
+// that are not listed are zero. Entries that share a position add up, and zero
+// entries are skipped, so the costs below only depend on the non-zero entries.
+impl<E: PairingEngine> DynCommitment<E> {
+    /// Commit to a sparse list of (position, input) entries with prover parameters.
+    /// The commitment is the same as the one of the dense list.
+    pub fn sparse_commit<I: IntoIterator<Item = (usize, E::Fr)>>(
+        pp: &DynProverParam<E>,
+        entries: I,
+    ) -> Result<Self, PointproofError> {
+        let n = pp.dimension();
+        Ok(Self {
+            n,
+            commitment: sparse_msm::<E, _>(&pp.g, entries, n, 0)?,
+        })
+    }
+
+    /// Open the input at a given position of a sparse list of (position, input)
+    /// entries. The witness is the same as the one of the dense list.
+    pub fn sparse_open<I: IntoIterator<Item = (usize, E::Fr)>>(
+        pp: &DynProverParam<E>,
+        entries: I,
+        pos: usize,
+    ) -> Result<E::G1Projective, PointproofError> {
+        let n = pp.dimension();
+        check_position(pos, n)?;
+
+        // the entry at pos itself meets g[n], which is zero
+        sparse_msm::<E, _>(&pp.g, entries, n, n - pos)
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Commitment<E, N> {
+    /// Commit to a sparse list of (position, input) entries with prover parameters.
+    /// The commitment is the same as the one of the dense list.
+    pub fn sparse_commit<I: IntoIterator<Item = (usize, E::Fr)>>(
+        pp: &ProverParam<E, N>,
+        entries: I,
+    ) -> Result<Self, PointproofError> {
+        Ok(Self {
+            inner: DynCommitment::sparse_commit(&pp.inner, entries)?,
+        })
+    }
+
+    /// Open the input at a given position of a sparse list of (position, input)
+    /// entries. The witness is the same as the one of the dense list.
+    pub fn sparse_open<I: IntoIterator<Item = (usize, E::Fr)>>(
+        pp: &ProverParam<E, N>,
+        entries: I,
+        pos: usize,
+    ) -> Result<E::G1Projective, PointproofError> {
+        DynCommitment::sparse_open(&pp.inner, entries, pos)
+    }
+}
+
+/// \sum_j m_j * g[offset + j] over the non-zero entries (j, m_j)
+fn sparse_msm<E: PairingEngine, I: IntoIterator<Item = (usize, E::Fr)>>(
+    g: &[E::G1Affine],
+    entries: I,
+    n: usize,
+    offset: usize,
+) -> Result<E::G1Projective, PointproofError> {
+    let mut bases = Vec::new();
+    let mut scalars = Vec::new();
+    for (pos, input) in entries {
+        check_position(pos, n)?;
+        if !input.is_zero() {
+            bases.push(g[offset + pos]);
+            scalars.push(input.into_repr());
+        }
+    }
+    Ok(VariableBaseMSM::multi_scalar_mul(&bases, &scalars))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::param::VerifierParam;
+    use crate::CommitmentScheme;
+    use crate::StructuredReferenceString;
+    use ark_bn254::Bn254;
+    use ark_ec::ProjectiveCurve;
+    use ark_serialize::CanonicalSerialize;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    const NUM_TEST: usize = 10;
+
+    macro_rules! test_sparse_commit_opening {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            let mut message = vec![<$engine as PairingEngine>::Fr::zero(); $dim];
+            let mut entries = Vec::new();
+            for _ in 0..NUM_TEST {
+                let pos = (rng.next_u32() % $dim) as usize;
+                let input = <$engine as PairingEngine>::Fr::rand(&mut rng);
+                // entries that share a position add up
+                message[pos] += input;
+                entries.push((pos, input));
+            }
+            entries.push(((rng.next_u32() % $dim) as usize, Zero::zero()));
+
+            let commitment =
+                Commitment::<$engine, $dim>::sparse_commit(&prover_param, entries.iter().copied())
+                    .unwrap();
+            assert_eq!(
+                commitment,
+                Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap()
+            );
+            let mut sparse_bytes = Vec::new();
+            commitment.serialize(&mut sparse_bytes).unwrap();
+            let mut dense_bytes = Vec::new();
+            Commitment::<$engine, $dim>::commit(&prover_param, &message)
+                .unwrap()
+                .serialize(&mut dense_bytes)
+                .unwrap();
+            assert_eq!(sparse_bytes, dense_bytes);
+
+            // the positions of the entries, and some zero positions
+            let mut positions: Vec<usize> = entries.iter().map(|&(pos, _)| pos).collect();
+            positions.extend([0, $dim - 1]);
+            for pos in positions {
+                let witness = Commitment::<$engine, $dim>::sparse_open(
+                    &prover_param,
+                    entries.iter().copied(),
+                    pos,
+                )
+                .unwrap();
+                assert_eq!(
+                    witness.into_affine(),
+                    Commitment::<$engine, $dim>::open(&prover_param, &message, pos)
+                        .unwrap()
+                        .into_affine()
+                );
+                assert!(commitment
+                    .verify(&verifier_param, &message[pos], pos, &witness)
+                    .unwrap());
+            }
+
+            // an empty list commits to the zero vector
+            assert_eq!(
+                Commitment::<$engine, $dim>::sparse_commit(&prover_param, []).unwrap(),
+                Commitment::<$engine, $dim>::commit(&prover_param, &[]).unwrap()
+            );
+
+            assert!(matches!(
+                Commitment::<$engine, $dim>::sparse_commit(&prover_param, [($dim, message[0])]),
+                Err(PointproofError::PositionOutOfRange { .. })
+            ));
+            assert!(matches!(
+                Commitment::<$engine, $dim>::sparse_open(&prover_param, [($dim, message[0])], 0),
+                Err(PointproofError::PositionOutOfRange { .. })
+            ));
+            assert!(matches!(
+                Commitment::<$engine, $dim>::sparse_open(&prover_param, entries, $dim),
+                Err(PointproofError::PositionOutOfRange { .. })
+            ));
+        };
+    }
+
+    #[test]
+    fn test_sparse_commit_opening() {
+        test_sparse_commit_opening!(Bn254, 128, "bn254");
+    }
+}
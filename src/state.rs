@@ -0,0 +1,286 @@
+use crate::error::check_dimension;
+use crate::error::check_input_length;
+use crate::error::check_position;
+use crate::param::DynProverParam;
+use crate::param::ProverParam;
+use crate::Commitment;
+use crate::CommitmentScheme;
+use crate::DynCommitment;
+use crate::PointproofError;
+use ark_ec::PairingEngine;
+use ark_std::Zero;
+use std::collections::BTreeMap;
+
+/// A vector together with its commitment and a cache of witnesses, which are
+/// all kept in sync when the vector is updated
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynProverState<E: PairingEngine> {
+    pub(crate) inputs: Vec<E::Fr>,
+    pub(crate) commitment: DynCommitment<E>,
+    pub(crate) witnesses: BTreeMap<usize, E::G1Projective>,
+}
+
+/// A `DynProverState` of dimension `N`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProverState<E: PairingEngine, const N: usize> {
+    pub(crate) inner: DynProverState<E>,
+}
+
+impl<E: PairingEngine> DynProverState<E> {
+    /// Commit to a list of inputs, padded with zeros to the dimension of `pp`
+    pub fn new(pp: &DynProverParam<E>, inputs: &[E::Fr]) -> Result<Self, PointproofError> {
+        let n = pp.dimension();
+        check_input_length(inputs.len(), n)?;
+
+        let commitment = DynCommitment::commit(pp, inputs)?;
+        let mut inputs = inputs.to_vec();
+        inputs.resize(n, E::Fr::zero());
+        Ok(Self {
+            inputs,
+            commitment,
+            witnesses: BTreeMap::new(),
+        })
+    }
+
+    /// The dimension of the committed vector
+    pub fn dimension(&self) -> usize {
+        self.commitment.n
+    }
+
+    /// The committed vector
+    pub fn inputs(&self) -> &[E::Fr] {
+        &self.inputs
+    }
+
+    /// The commitment to the vector
+    pub fn commitment(&self) -> &DynCommitment<E> {
+        &self.commitment
+    }
+
+    /// The witness of the input at `pos`, computed and cached if it is not yet
+    pub fn witness(
+        &mut self,
+        pp: &DynProverParam<E>,
+        pos: usize,
+    ) -> Result<E::G1Projective, PointproofError> {
+        check_dimension(self.dimension(), pp.dimension())?;
+        check_position(pos, self.dimension())?;
+
+        if let Some(witness) = self.witnesses.get(&pos) {
+            return Ok(*witness);
+        }
+        let witness = DynCommitment::open(pp, &self.inputs, pos)?;
+        self.witnesses.insert(pos, witness);
+        Ok(witness)
+    }
+
+    /// The cached witness of the input at `pos`, if any
+    pub fn cached_witness(&self, pos: usize) -> Option<&E::G1Projective> {
+        self.witnesses.get(&pos)
+    }
+
+    /// Compute and cache the witnesses of all the positions with `open_all`
+    pub fn cache_all_witnesses(&mut self, pp: &DynProverParam<E>) -> Result<(), PointproofError> {
+        check_dimension(self.dimension(), pp.dimension())?;
+
+        let witnesses = DynCommitment::open_all(pp, &self.inputs)?;
+        self.witnesses = witnesses.into_iter().enumerate().collect();
+        Ok(())
+    }
+
+    /// Remove the witness of the input at `pos` from the cache
+    pub fn evict_witness(&mut self, pos: usize) -> Option<E::G1Projective> {
+        self.witnesses.remove(&pos)
+    }
+
+    /// Set the input at `pos` to `new_input`, and update the commitment and
+    /// every cached witness accordingly
+    pub fn update(
+        &mut self,
+        pp: &DynProverParam<E>,
+        pos: usize,
+        new_input: &E::Fr,
+    ) -> Result<(), PointproofError> {
+        check_dimension(self.dimension(), pp.dimension())?;
+        check_position(pos, self.dimension())?;
+
+        let old_input = self.inputs[pos];
+        let positions: Vec<usize> = self.witnesses.keys().copied().collect();
+        let witnesses: Vec<E::G1Projective> = self.witnesses.values().copied().collect();
+        let witnesses = DynCommitment::batch_update_witnesses(
+            pp, &witnesses, &positions, pos, &old_input, new_input,
+        )?;
+        self.commitment.update(pp, pos, &old_input, new_input)?;
+
+        for (cached, witness) in self.witnesses.values_mut().zip(witnesses) {
+            *cached = witness;
+        }
+        self.inputs[pos] = *new_input;
+        Ok(())
+    }
+}
+
+impl<E: PairingEngine, const N: usize> ProverState<E, N> {
+    /// Commit to a list of inputs, padded with zeros to N
+    pub fn new(pp: &ProverParam<E, N>, inputs: &[E::Fr]) -> Result<Self, PointproofError> {
+        Ok(Self {
+            inner: DynProverState::new(&pp.inner, inputs)?,
+        })
+    }
+
+    /// The committed vector
+    pub fn inputs(&self) -> &[E::Fr] {
+        self.inner.inputs()
+    }
+
+    /// The commitment to the vector
+    pub fn commitment(&self) -> Commitment<E, N> {
+        Commitment {
+            inner: self.inner.commitment,
+        }
+    }
+
+    /// The witness of the input at `pos`, computed and cached if it is not yet
+    pub fn witness(
+        &mut self,
+        pp: &ProverParam<E, N>,
+        pos: usize,
+    ) -> Result<E::G1Projective, PointproofError> {
+        self.inner.witness(&pp.inner, pos)
+    }
+
+    /// The cached witness of the input at `pos`, if any
+    pub fn cached_witness(&self, pos: usize) -> Option<&E::G1Projective> {
+        self.inner.cached_witness(pos)
+    }
+
+    /// Compute and cache the witnesses of all the positions with `open_all`
+    pub fn cache_all_witnesses(&mut self, pp: &ProverParam<E, N>) -> Result<(), PointproofError> {
+        self.inner.cache_all_witnesses(&pp.inner)
+    }
+
+    /// Remove the witness of the input at `pos` from the cache
+    pub fn evict_witness(&mut self, pos: usize) -> Option<E::G1Projective> {
+        self.inner.evict_witness(pos)
+    }
+
+    /// Set the input at `pos` to `new_input`, and update the commitment and
+    /// every cached witness accordingly
+    pub fn update(
+        &mut self,
+        pp: &ProverParam<E, N>,
+        pos: usize,
+        new_input: &E::Fr,
+    ) -> Result<(), PointproofError> {
+        self.inner.update(&pp.inner, pos, new_input)
+    }
+}
+
+impl<E: PairingEngine, const N: usize> From<ProverState<E, N>> for DynProverState<E> {
+    fn from(state: ProverState<E, N>) -> Self {
+        state.inner
+    }
+}
+
+impl<E: PairingEngine, const N: usize> TryFrom<DynProverState<E>> for ProverState<E, N> {
+    type Error = PointproofError;
+
+    fn try_from(state: DynProverState<E>) -> Result<Self, PointproofError> {
+        check_dimension(N, state.dimension())?;
+        Ok(Self { inner: state })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::param::VerifierParam;
+    use crate::StructuredReferenceString;
+    use ark_bn254::Bn254;
+    use ark_serialize::CanonicalDeserialize;
+    use ark_serialize::CanonicalSerialize;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    const NUM_TEST: usize = 10;
+
+    macro_rules! test_prover_state {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            let message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim / 2)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let mut state = ProverState::<$engine, $dim>::new(&prover_param, &message).unwrap();
+            assert_eq!(state.inputs().len(), $dim);
+
+            let positions: Vec<usize> = (0..NUM_TEST)
+                .map(|_| (rng.next_u32() % $dim) as usize)
+                .collect();
+            for &pos in positions.iter() {
+                let witness = state.witness(&prover_param, pos).unwrap();
+                assert_eq!(state.cached_witness(pos), Some(&witness));
+            }
+
+            for i in 0..NUM_TEST {
+                // also cover the case where the updated position is a cached one
+                let pos = if i % 2 == 0 {
+                    positions[i]
+                } else {
+                    (rng.next_u32() % $dim) as usize
+                };
+                let new_input = <$engine as PairingEngine>::Fr::rand(&mut rng);
+                state.update(&prover_param, pos, &new_input).unwrap();
+                assert_eq!(state.inputs()[pos], new_input);
+            }
+
+            let commitment = state.commitment();
+            assert_eq!(
+                commitment,
+                Commitment::<$engine, $dim>::commit(&prover_param, state.inputs()).unwrap()
+            );
+            for &pos in positions.iter() {
+                let witness = *state.cached_witness(pos).unwrap();
+                assert_eq!(
+                    witness,
+                    Commitment::<$engine, $dim>::open(&prover_param, state.inputs(), pos).unwrap()
+                );
+                assert!(commitment
+                    .verify(&verifier_param, &state.inputs()[pos], pos, &witness)
+                    .unwrap());
+            }
+
+            state.evict_witness(positions[0]);
+            assert!(state.cached_witness(positions[0]).is_none());
+            state.cache_all_witnesses(&prover_param).unwrap();
+            assert!(state.cached_witness(positions[0]).is_some());
+
+            // snapshot and restore
+            let mut buf = Vec::new();
+            state.serialize(&mut buf).unwrap();
+            assert_eq!(buf.len(), state.serialized_size());
+            let restored = ProverState::<$engine, $dim>::deserialize(&buf[..]).unwrap();
+            assert_eq!(restored, state);
+            let restored = DynProverState::<$engine>::deserialize(&buf[..]).unwrap();
+            assert_eq!(restored, state.inner);
+            assert!(ProverState::<$engine, { $dim / 2 }>::deserialize(&buf[..]).is_err());
+            assert!(ProverState::<$engine, $dim>::deserialize(&buf[..buf.len() - 1]).is_err());
+
+            assert!(matches!(
+                state.update(&prover_param, $dim, &message[0]),
+                Err(PointproofError::PositionOutOfRange { .. })
+            ));
+        };
+    }
+
+    #[test]
+    fn test_prover_state() {
+        test_prover_state!(Bn254, 64, "bn254");
+    }
+}
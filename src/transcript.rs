@@ -0,0 +1,194 @@
+use crate::DynCommitment;
+use ark_ec::PairingEngine;
+use ark_ec::ProjectiveCurve;
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use sha2::Digest;
+use sha2::Sha512;
+use std::marker::PhantomData;
+
+/// A Fiat-Shamir transcript that absorbs labelled messages and squeezes challenges
+///
+/// A transcript is a running SHA-512 hash of labelled messages. Each message is
+/// absorbed as
+/// ```text
+/// label length (8 bytes, little endian) || label || data length (8 bytes, little endian) || data
+/// ```
+/// where the data is encoded as follows:
+/// - bytes are absorbed as they are;
+/// - positions are 8-byte little-endian integers, and lists of positions are
+///   the concatenation of their encodings;
+/// - scalars use the compressed arkworks encoding, i.e., little-endian integers
+///   of the size of the field, and lists of scalars are the concatenation of
+///   their encodings;
+/// - G1 and G2 points use the compressed arkworks encoding, i.e., the
+///   little-endian x coordinate with the flags in the last byte;
+/// - a commitment is its dimension as a position followed by its point.
+///
+/// A transcript starts by absorbing its domain separator under the label `"domain"`.
+/// A challenge absorbs its label with empty data, and is the SHA-512 digest of the
+/// transcript so far read as a little-endian integer and reduced modulo the order
+/// of the scalar field; the digest is then absorbed under the label `"challenge"`.
+///
+/// The coefficients of the crate are squeezed under the label `"coefficient"`, from
+/// transcripts with the following domain separators and messages:
+/// - `"pointproofs same commitment aggregation"`: the `"commitment"`, its
+///   `"positions"` and its `"inputs"`;
+/// - `"pointproofs cross commitment aggregation"`: the `"number of commitments"`,
+///   then the `"commitment"`, `"positions"` and `"inputs"` of each commitment;
+/// - `"pointproofs batch verification"`: the `"number of openings"`, then the
+///   `"commitment"`, `"position"`, `"input"` and `"witness"` of each opening;
+/// - `"pointproofs length bound"`: the `"commitment"` and the `"bound"`.
+#[derive(Debug, Clone)]
+pub struct Transcript<E: PairingEngine> {
+    hasher: Sha512,
+    engine: PhantomData<E>,
+}
+
+impl<E: PairingEngine> Transcript<E> {
+    /// A new transcript for the given domain separator
+    pub fn new(domain: &[u8]) -> Self {
+        let mut transcript = Self {
+            hasher: Sha512::new(),
+            engine: PhantomData,
+        };
+        transcript.append_bytes(b"domain", domain);
+        transcript
+    }
+
+    /// Absorb a byte string
+    pub fn append_bytes(&mut self, label: &[u8], data: &[u8]) {
+        self.hasher.update((label.len() as u64).to_le_bytes());
+        self.hasher.update(label);
+        self.hasher.update((data.len() as u64).to_le_bytes());
+        self.hasher.update(data);
+    }
+
+    /// Absorb a position
+    pub fn append_position(&mut self, label: &[u8], pos: usize) {
+        self.append_positions(label, &[pos]);
+    }
+
+    /// Absorb a list of positions
+    pub fn append_positions(&mut self, label: &[u8], positions: &[usize]) {
+        let mut buf = Vec::with_capacity(positions.len() * 8);
+        for &pos in positions.iter() {
+            buf.extend_from_slice(&(pos as u64).to_le_bytes());
+        }
+        self.append_bytes(label, &buf);
+    }
+
+    /// Absorb a scalar
+    pub fn append_scalar(&mut self, label: &[u8], scalar: &E::Fr) {
+        self.append_scalars(label, &[*scalar]);
+    }
+
+    /// Absorb a list of scalars
+    pub fn append_scalars(&mut self, label: &[u8], scalars: &[E::Fr]) {
+        let mut buf = Vec::new();
+        for scalar in scalars.iter() {
+            append_serialized(&mut buf, scalar);
+        }
+        self.append_bytes(label, &buf);
+    }
+
+    /// Absorb a G1 point
+    pub fn append_g1(&mut self, label: &[u8], point: &E::G1Affine) {
+        let mut buf = Vec::new();
+        append_serialized(&mut buf, point);
+        self.append_bytes(label, &buf);
+    }
+
+    /// Absorb a G2 point
+    pub fn append_g2(&mut self, label: &[u8], point: &E::G2Affine) {
+        let mut buf = Vec::new();
+        append_serialized(&mut buf, point);
+        self.append_bytes(label, &buf);
+    }
+
+    /// Absorb a commitment together with its dimension
+    pub fn append_commitment(&mut self, label: &[u8], com: &DynCommitment<E>) {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(com.n as u64).to_le_bytes());
+        append_serialized(&mut buf, &com.commitment.into_affine());
+        self.append_bytes(label, &buf);
+    }
+
+    /// Squeeze a challenge
+    pub fn challenge_scalar(&mut self, label: &[u8]) -> E::Fr {
+        self.append_bytes(label, &[]);
+        let digest = self.hasher.clone().finalize();
+        self.append_bytes(b"challenge", &digest);
+        E::Fr::from_le_bytes_mod_order(&digest)
+    }
+
+    /// Squeeze `num` challenges
+    pub fn challenge_scalars(&mut self, label: &[u8], num: usize) -> Vec<E::Fr> {
+        (0..num).map(|_| self.challenge_scalar(label)).collect()
+    }
+}
+
+fn append_serialized<T: CanonicalSerialize>(buf: &mut Vec<u8>, value: &T) {
+    value
+        .serialize(buf)
+        .expect("serializing into a vector never fails");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ark_bn254::Bn254;
+    use ark_bn254::Fr;
+    use ark_ec::AffineCurve;
+
+    #[test]
+    fn test_transcript() {
+        let mut transcript = Transcript::<Bn254>::new(b"test");
+        transcript.append_positions(b"positions", &[1, 2]);
+        transcript.append_scalar(b"scalar", &Fr::from(3u64));
+        transcript.append_g1(
+            b"g1",
+            &<Bn254 as PairingEngine>::G1Affine::prime_subgroup_generator(),
+        );
+        let challenge = transcript.challenge_scalar(b"coefficient");
+
+        // the same hashing spelled out byte by byte
+        let mut data = Vec::new();
+        for (label, value) in [
+            (&b"domain"[..], &b"test"[..]),
+            (
+                b"positions",
+                &[1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0],
+            ),
+        ] {
+            data.extend_from_slice(&(label.len() as u64).to_le_bytes());
+            data.extend_from_slice(label);
+            data.extend_from_slice(&(value.len() as u64).to_le_bytes());
+            data.extend_from_slice(value);
+        }
+        let mut scalar = [0u8; 32];
+        scalar[0] = 3;
+        // the compressed generator (1, 2) is x = 1 without flags
+        let mut g1 = [0u8; 32];
+        g1[0] = 1;
+        for (label, value) in [
+            (&b"scalar"[..], &scalar[..]),
+            (b"g1", &g1[..]),
+            (b"coefficient", &[]),
+        ] {
+            data.extend_from_slice(&(label.len() as u64).to_le_bytes());
+            data.extend_from_slice(label);
+            data.extend_from_slice(&(value.len() as u64).to_le_bytes());
+            data.extend_from_slice(value);
+        }
+        let digest = Sha512::digest(&data);
+        assert_eq!(challenge, Fr::from_le_bytes_mod_order(&digest));
+
+        // each challenge changes the state
+        let next = transcript.challenge_scalar(b"coefficient");
+        assert_ne!(challenge, next);
+        let mut fresh = Transcript::<Bn254>::new(b"test");
+        fresh.append_positions(b"positions", &[1, 2]);
+        assert_ne!(fresh.challenge_scalar(b"coefficient"), challenge);
+    }
+}
@@ -0,0 +1,328 @@
+use crate::error::check_dimension;
+use crate::error::check_position;
+use crate::error::check_same_length;
+use crate::param::DynProverParam;
+use crate::param::ProverParam;
+use crate::Commitment;
+use crate::DynCommitment;
+use crate::PointproofError;
+use ark_ec::msm::VariableBaseMSM;
+use ark_ec::AffineCurve;
+use ark_ec::PairingEngine;
+use ark_ff::PrimeField;
+#[cfg(feature = "parallel")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "parallel")]
+use rayon::iter::IntoParallelRefIterator;
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+
+impl<E: PairingEngine> DynCommitment<E> {
+    /// Update the commitment in place after the input at `pos` changed
+    /// from `old_input` to `new_input`
+    pub fn update(
+        &mut self,
+        pp: &DynProverParam<E>,
+        pos: usize,
+        old_input: &E::Fr,
+        new_input: &E::Fr,
+    ) -> Result<(), PointproofError> {
+        check_dimension(self.n, pp.dimension())?;
+        check_position(pos, self.n)?;
+
+        let delta = *new_input - old_input;
+        self.commitment += pp.g[pos].mul(delta.into_repr());
+        Ok(())
+    }
+
+    /// Update the commitment in place after the inputs at `positions` changed
+    /// from `old_inputs` to `new_inputs`
+    pub fn batch_update(
+        &mut self,
+        pp: &DynProverParam<E>,
+        positions: &[usize],
+        old_inputs: &[E::Fr],
+        new_inputs: &[E::Fr],
+    ) -> Result<(), PointproofError> {
+        check_dimension(self.n, pp.dimension())?;
+        check_same_length(positions.len(), old_inputs.len())?;
+        check_same_length(positions.len(), new_inputs.len())?;
+        for &pos in positions.iter() {
+            check_position(pos, self.n)?;
+        }
+
+        let bases: Vec<E::G1Affine> = positions.iter().map(|&pos| pp.g[pos]).collect();
+        let scalars: Vec<<E::Fr as PrimeField>::BigInt> = old_inputs
+            .iter()
+            .zip(new_inputs.iter())
+            .map(|(old, new)| (*new - old).into_repr())
+            .collect();
+        self.commitment += VariableBaseMSM::multi_scalar_mul(&bases, &scalars);
+        Ok(())
+    }
+
+    /// Update the witness of the input at `witness_pos` after the input at
+    /// `updated_pos` changed from `old_input` to `new_input`.
+    ///
+    /// The witness of position j carries m_i * g\[n - j + i\] for every i != j,
+    /// so a change at i = j leaves it unchanged.
+    pub fn update_witness(
+        pp: &DynProverParam<E>,
+        witness: &E::G1Projective,
+        witness_pos: usize,
+        updated_pos: usize,
+        old_input: &E::Fr,
+        new_input: &E::Fr,
+    ) -> Result<E::G1Projective, PointproofError> {
+        let n = pp.dimension();
+        check_position(witness_pos, n)?;
+        check_position(updated_pos, n)?;
+
+        if witness_pos == updated_pos {
+            return Ok(*witness);
+        }
+        let delta = *new_input - old_input;
+        Ok(*witness + pp.g[n - witness_pos + updated_pos].mul(delta.into_repr()))
+    }
+
+    /// Update the witnesses of the inputs at `witness_positions` after the
+    /// input at `updated_pos` changed from `old_input` to `new_input`
+    pub fn batch_update_witnesses(
+        pp: &DynProverParam<E>,
+        witnesses: &[E::G1Projective],
+        witness_positions: &[usize],
+        updated_pos: usize,
+        old_input: &E::Fr,
+        new_input: &E::Fr,
+    ) -> Result<Vec<E::G1Projective>, PointproofError> {
+        let n = pp.dimension();
+        check_same_length(witnesses.len(), witness_positions.len())?;
+        for &pos in witness_positions.iter() {
+            check_position(pos, n)?;
+        }
+        check_position(updated_pos, n)?;
+
+        let delta = (*new_input - old_input).into_repr();
+        Ok(ark_std::cfg_iter!(witnesses)
+            .zip(witness_positions)
+            .map(|(witness, &witness_pos)| {
+                if witness_pos == updated_pos {
+                    *witness
+                } else {
+                    *witness + pp.g[n - witness_pos + updated_pos].mul(delta)
+                }
+            })
+            .collect())
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Commitment<E, N> {
+    /// Update the commitment in place after the input at `pos` changed
+    /// from `old_input` to `new_input`
+    pub fn update(
+        &mut self,
+        pp: &ProverParam<E, N>,
+        pos: usize,
+        old_input: &E::Fr,
+        new_input: &E::Fr,
+    ) -> Result<(), PointproofError> {
+        self.inner.update(&pp.inner, pos, old_input, new_input)
+    }
+
+    /// Update the commitment in place after the inputs at `positions` changed
+    /// from `old_inputs` to `new_inputs`
+    pub fn batch_update(
+        &mut self,
+        pp: &ProverParam<E, N>,
+        positions: &[usize],
+        old_inputs: &[E::Fr],
+        new_inputs: &[E::Fr],
+    ) -> Result<(), PointproofError> {
+        self.inner
+            .batch_update(&pp.inner, positions, old_inputs, new_inputs)
+    }
+
+    /// Update the witness of the input at `witness_pos` after the input at
+    /// `updated_pos` changed from `old_input` to `new_input`
+    pub fn update_witness(
+        pp: &ProverParam<E, N>,
+        witness: &E::G1Projective,
+        witness_pos: usize,
+        updated_pos: usize,
+        old_input: &E::Fr,
+        new_input: &E::Fr,
+    ) -> Result<E::G1Projective, PointproofError> {
+        DynCommitment::update_witness(
+            &pp.inner,
+            witness,
+            witness_pos,
+            updated_pos,
+            old_input,
+            new_input,
+        )
+    }
+
+    /// Update the witnesses of the inputs at `witness_positions` after the
+    /// input at `updated_pos` changed from `old_input` to `new_input`
+    pub fn batch_update_witnesses(
+        pp: &ProverParam<E, N>,
+        witnesses: &[E::G1Projective],
+        witness_positions: &[usize],
+        updated_pos: usize,
+        old_input: &E::Fr,
+        new_input: &E::Fr,
+    ) -> Result<Vec<E::G1Projective>, PointproofError> {
+        DynCommitment::batch_update_witnesses(
+            &pp.inner,
+            witnesses,
+            witness_positions,
+            updated_pos,
+            old_input,
+            new_input,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::param::VerifierParam;
+    use crate::CommitmentScheme;
+    use crate::StructuredReferenceString;
+    use ark_bn254::Bn254;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    const NUM_TEST: usize = 10;
+
+    macro_rules! test_commitment_update {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+
+            let mut message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let mut commitment =
+                Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap();
+
+            for _ in 0..NUM_TEST {
+                let pos = (rng.next_u32() % $dim) as usize;
+                let new_input = <$engine as PairingEngine>::Fr::rand(&mut rng);
+                commitment
+                    .update(&prover_param, pos, &message[pos], &new_input)
+                    .unwrap();
+                message[pos] = new_input;
+                assert_eq!(
+                    commitment,
+                    Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap()
+                );
+            }
+
+            let positions: Vec<usize> = (0..NUM_TEST)
+                .map(|_| (rng.next_u32() % $dim) as usize)
+                .collect();
+            let mut old_inputs = Vec::new();
+            let mut new_inputs = Vec::new();
+            for &pos in positions.iter() {
+                let new_input = <$engine as PairingEngine>::Fr::rand(&mut rng);
+                old_inputs.push(message[pos]);
+                new_inputs.push(new_input);
+                message[pos] = new_input;
+            }
+            commitment
+                .batch_update(&prover_param, &positions, &old_inputs, &new_inputs)
+                .unwrap();
+            assert_eq!(
+                commitment,
+                Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap()
+            );
+        };
+    }
+
+    macro_rules! test_witness_update {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            let mut message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let mut commitment =
+                Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap();
+
+            let witness_positions: Vec<usize> = (0..NUM_TEST)
+                .map(|_| (rng.next_u32() % $dim) as usize)
+                .collect();
+            let mut witnesses: Vec<<$engine as PairingEngine>::G1Projective> = witness_positions
+                .iter()
+                .map(|&pos| {
+                    Commitment::<$engine, $dim>::open(&prover_param, &message, pos).unwrap()
+                })
+                .collect();
+
+            for i in 0..NUM_TEST {
+                // also cover the case where the updated position is a cached one
+                let updated_pos = if i % 2 == 0 {
+                    witness_positions[i]
+                } else {
+                    (rng.next_u32() % $dim) as usize
+                };
+                let new_input = <$engine as PairingEngine>::Fr::rand(&mut rng);
+                let old_input = message[updated_pos];
+
+                let witness = Commitment::<$engine, $dim>::update_witness(
+                    &prover_param,
+                    &witnesses[0],
+                    witness_positions[0],
+                    updated_pos,
+                    &old_input,
+                    &new_input,
+                )
+                .unwrap();
+                witnesses = Commitment::<$engine, $dim>::batch_update_witnesses(
+                    &prover_param,
+                    &witnesses,
+                    &witness_positions,
+                    updated_pos,
+                    &old_input,
+                    &new_input,
+                )
+                .unwrap();
+                assert_eq!(witness, witnesses[0]);
+
+                commitment
+                    .update(&prover_param, updated_pos, &old_input, &new_input)
+                    .unwrap();
+                message[updated_pos] = new_input;
+                for (&pos, witness) in witness_positions.iter().zip(witnesses.iter()) {
+                    assert_eq!(
+                        *witness,
+                        Commitment::<$engine, $dim>::open(&prover_param, &message, pos).unwrap()
+                    );
+                    assert!(commitment
+                        .verify(&verifier_param, &message[pos], pos, witness)
+                        .unwrap());
+                }
+            }
+        };
+    }
+
+    #[test]
+    fn test_commitment_update() {
+        test_commitment_update!(Bn254, 128, "bn254");
+    }
+
+    #[test]
+    fn test_witness_update() {
+        test_witness_update!(Bn254, 128, "bn254");
+    }
+}
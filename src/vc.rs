@@ -1,7 +1,14 @@
+use crate::error::check_dimension;
+use crate::error::check_input_length;
+use crate::error::check_position;
+use crate::param::DynProverParam;
+use crate::param::DynVerifierParam;
 use crate::param::ProverParam;
 use crate::param::VerifierParam;
 use crate::Commitment;
 use crate::CommitmentScheme;
+use crate::DynCommitment;
+use crate::PointproofError;
 use ark_ec::msm::VariableBaseMSM;
 use ark_ec::AffineCurve;
 use ark_ec::PairingEngine;
@@ -10,34 +17,45 @@ use ark_ff::Field;
 use ark_ff::PrimeField;
 use std::ops::Neg;
 
-impl<E: PairingEngine, const N: usize> CommitmentScheme for Commitment<E, N> {
-    type ProverParam = ProverParam<E, N>;
-    type VerifierParam = VerifierParam<E, N>;
+impl<E: PairingEngine> CommitmentScheme for DynCommitment<E> {
+    type ProverParam = DynProverParam<E>;
+    type VerifierParam = DynVerifierParam<E>;
     type MessageUnit = E::Fr;
     type Commitment = Self;
     type Witness = E::G1Projective;
 
     /// Commit to a list of inputs with prover parameters
-    fn commit(pp: &Self::ProverParam, inputs: &[Self::MessageUnit]) -> Self {
-        assert!(inputs.len() <= N);
+    fn commit(
+        pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+    ) -> Result<Self, PointproofError> {
+        let n = pp.dimension();
+        check_input_length(inputs.len(), n)?;
 
         let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
             inputs.iter().map(|x| x.into_repr()).collect();
-        Self {
+        Ok(Self {
+            n,
             commitment: VariableBaseMSM::multi_scalar_mul(&pp.g[0..inputs.len()], scalars.as_ref()),
-        }
+        })
     }
 
     /// Open an input at a given position
-    fn open(pp: &Self::ProverParam, inputs: &[Self::MessageUnit], pos: usize) -> Self::Witness {
-        assert!(inputs.len() <= N);
+    fn open(
+        pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+        pos: usize,
+    ) -> Result<Self::Witness, PointproofError> {
+        let n = pp.dimension();
+        check_input_length(inputs.len(), n)?;
+        check_position(pos, n)?;
 
         let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
             inputs.iter().map(|x| x.into_repr()).collect();
-        VariableBaseMSM::multi_scalar_mul(
-            pp.g[N - pos..N - pos + inputs.len()].as_ref(),
+        Ok(VariableBaseMSM::multi_scalar_mul(
+            pp.g[n - pos..n - pos + inputs.len()].as_ref(),
             scalars.as_ref(),
-        )
+        ))
     }
 
     /// Verify the input/witness pair is correct
@@ -47,30 +65,74 @@ impl<E: PairingEngine, const N: usize> CommitmentScheme for Commitment<E, N> {
         input: &Self::MessageUnit,
         pos: usize,
         witness: &Self::Witness,
-    ) -> bool {
-        let input_inverse = input.inverse().unwrap();
-
-        let com = self
-            .commitment
-            .mul(&input_inverse.into_repr())
-            .into_affine();
-        let proof = witness.mul(input_inverse.neg().into_repr()).into_affine();
-        let pairing_prod_inputs = vec![
-            (com.into(), vp.h[N - pos - 1].into()),
-            (proof.into(), E::G2Affine::prime_subgroup_generator().into()),
+    ) -> Result<bool, PointproofError> {
+        let n = self.n;
+        check_dimension(n, vp.dimension())?;
+        check_position(pos, n)?;
+
+        // e(C, h[n - pos - 1]) * e(-witness, H) = t^{input}
+        let pairing_prod_inputs = [
+            (
+                self.commitment.into_affine().into(),
+                vp.h[n - pos - 1].into(),
+            ),
+            (
+                witness.neg().into_affine().into(),
+                E::G2Affine::prime_subgroup_generator().into(),
+            ),
         ];
-        E::product_of_pairings(pairing_prod_inputs.iter()) == vp.t
+        Ok(E::product_of_pairings(pairing_prod_inputs.iter()) == vp.t.pow(input.into_repr()))
+    }
+}
+
+impl<E: PairingEngine, const N: usize> CommitmentScheme for Commitment<E, N> {
+    type ProverParam = ProverParam<E, N>;
+    type VerifierParam = VerifierParam<E, N>;
+    type MessageUnit = E::Fr;
+    type Commitment = Self;
+    type Witness = E::G1Projective;
+
+    /// Commit to a list of inputs with prover parameters
+    fn commit(
+        pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+    ) -> Result<Self, PointproofError> {
+        Ok(Self {
+            inner: DynCommitment::commit(&pp.inner, inputs)?,
+        })
+    }
+
+    /// Open an input at a given position
+    fn open(
+        pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+        pos: usize,
+    ) -> Result<Self::Witness, PointproofError> {
+        DynCommitment::open(&pp.inner, inputs, pos)
+    }
+
+    /// Verify the input/witness pair is correct
+    fn verify(
+        &self,
+        vp: &Self::VerifierParam,
+        input: &Self::MessageUnit,
+        pos: usize,
+        witness: &Self::Witness,
+    ) -> Result<bool, PointproofError> {
+        self.inner.verify(&vp.inner, input, pos, witness)
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::DynStructuredReferenceString;
     use crate::StructuredReferenceString;
     use ark_bn254::Bn254;
     use ark_std::rand::RngCore;
     use ark_std::test_rng;
     use ark_std::UniformRand;
+    use ark_std::Zero;
 
     const NUM_TEST: usize = 10;
 
@@ -78,19 +140,105 @@ mod test {
         ($engine: tt, $dim: expr, $disc: tt) => {
             let mut rng = test_rng();
 
-            let srs = StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng);
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
             let prover_param: ProverParam<$engine, $dim> = (&srs).into();
             let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
 
             let message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
                 .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
                 .collect();
-            let commitment = Commitment::<$engine, $dim>::commit(&prover_param, &message);
+            let commitment = Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap();
             for _ in (0..NUM_TEST) {
                 let pos = (rng.next_u32() % $dim) as usize;
-                let witness = Commitment::<$engine, $dim>::open(&prover_param, &message, pos);
-                assert!(commitment.verify(&verifier_param, &message[pos], pos, &witness))
+                let witness =
+                    Commitment::<$engine, $dim>::open(&prover_param, &message, pos).unwrap();
+                assert!(commitment
+                    .verify(&verifier_param, &message[pos], pos, &witness)
+                    .unwrap())
+            }
+
+            // zero inputs, including the positions beyond the message length
+            let mut message = message[..$dim / 2].to_vec();
+            message[0] = <$engine as PairingEngine>::Fr::zero();
+            let commitment = Commitment::<$engine, $dim>::commit(&prover_param, &message).unwrap();
+            for pos in [0, $dim / 2, $dim - 1] {
+                let witness =
+                    Commitment::<$engine, $dim>::open(&prover_param, &message, pos).unwrap();
+                let zero = <$engine as PairingEngine>::Fr::zero();
+                assert!(commitment
+                    .verify(&verifier_param, &zero, pos, &witness)
+                    .unwrap());
+                let one = <$engine as PairingEngine>::Fr::from(1u64);
+                assert!(!commitment
+                    .verify(&verifier_param, &one, pos, &witness)
+                    .unwrap());
             }
+
+            // malformed inputs are rejected
+            let witness = Commitment::<$engine, $dim>::open(&prover_param, &message, 0).unwrap();
+            let long_message = vec![<$engine as PairingEngine>::Fr::zero(); $dim + 1];
+            assert!(matches!(
+                Commitment::<$engine, $dim>::commit(&prover_param, &long_message),
+                Err(PointproofError::InputTooLong { .. })
+            ));
+            assert!(matches!(
+                Commitment::<$engine, $dim>::open(&prover_param, &message, $dim),
+                Err(PointproofError::PositionOutOfRange { .. })
+            ));
+            assert!(matches!(
+                commitment.verify(&verifier_param, &message[0], $dim, &witness),
+                Err(PointproofError::PositionOutOfRange { .. })
+            ));
+        };
+    }
+
+    macro_rules! test_dyn_commit_opening {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs = DynStructuredReferenceString::<$engine>::new_srs_for_testing($dim, &mut rng)
+                .unwrap();
+            let prover_param: DynProverParam<$engine> = (&srs).into();
+            let verifier_param: DynVerifierParam<$engine> = (&srs).into();
+            assert_eq!(prover_param.dimension(), $dim);
+            assert_eq!(verifier_param.dimension(), $dim);
+
+            let message: Vec<<$engine as PairingEngine>::Fr> = (0..$dim)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let commitment = DynCommitment::commit(&prover_param, &message).unwrap();
+            assert_eq!(commitment.dimension(), $dim);
+            let pos = (rng.next_u32() % $dim) as usize;
+            let witness = DynCommitment::open(&prover_param, &message, pos).unwrap();
+            assert!(commitment
+                .verify(&verifier_param, &message[pos], pos, &witness)
+                .unwrap());
+
+            // the typed wrappers agree with the runtime types of the same dimension
+            let typed_prover_param: ProverParam<$engine, $dim> =
+                prover_param.clone().try_into().unwrap();
+            let typed_commitment =
+                Commitment::<$engine, $dim>::commit(&typed_prover_param, &message).unwrap();
+            assert_eq!(DynCommitment::from(typed_commitment), commitment);
+            assert!(matches!(
+                ProverParam::<$engine, { $dim / 2 }>::try_from(prover_param.clone()),
+                Err(PointproofError::DimensionMismatch { .. })
+            ));
+            assert!(matches!(
+                Commitment::<$engine, { $dim / 2 }>::try_from(commitment),
+                Err(PointproofError::DimensionMismatch { .. })
+            ));
+
+            // commitments and parameters of different dimensions do not mix
+            let other_srs =
+                DynStructuredReferenceString::<$engine>::new_srs_for_testing($dim / 2, &mut rng)
+                    .unwrap();
+            let other_verifier_param: DynVerifierParam<$engine> = (&other_srs).into();
+            assert!(matches!(
+                commitment.verify(&other_verifier_param, &message[0], 0, &witness),
+                Err(PointproofError::DimensionMismatch { .. })
+            ));
         };
     }
 
@@ -98,4 +246,9 @@ mod test {
     fn test_single_commit_opening() {
         test_single_commit_opening!(Bn254, 128, "bn254");
     }
+
+    #[test]
+    fn test_dyn_commit_opening() {
+        test_dyn_commit_opening!(Bn254, 128, "bn254");
+    }
 }
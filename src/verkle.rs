@@ -0,0 +1,447 @@
+use crate::error::check_dimension;
+use crate::error::check_min_dimension;
+use crate::error::check_position;
+use crate::error::check_same_length;
+use crate::param::DynProverParam;
+use crate::param::DynVerifierParam;
+use crate::param::ProverParam;
+use crate::param::VerifierParam;
+use crate::Commitment;
+use crate::CommitmentScheme;
+use crate::DynCommitment;
+use crate::PointproofError;
+use ark_ec::PairingEngine;
+use ark_ec::ProjectiveCurve;
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalDeserialize;
+use ark_serialize::CanonicalSerialize;
+use ark_serialize::Read;
+use ark_serialize::SerializationError;
+use ark_serialize::Write;
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+#[cfg(feature = "parallel")]
+use rayon::slice::ParallelSlice;
+use sha2::Digest;
+use sha2::Sha512;
+
+/// Domain separator for hashing the commitment of a node into its parent
+const NODE_DOMAIN: &[u8] = b"pointproofs verkle node";
+
+/// The smallest arity for which the levels of a tree shrink
+const MIN_ARITY: usize = 2;
+
+// A tree of arity n over a vector of length len: the leaves commit to chunks of
+// n inputs, and each inner node commits to the hashes of the commitments of up
+// to n children, up to a single root. Position i of the vector is position
+// (i / n^k) % n of its node at level k, so a path from a leaf to the root is
+// opened with one cross-commitment aggregated proof, and a proof consists of
+// the depth - 1 commitments below the root together with a single G1 element.
+// The depth is log_n(len), e.g., 3 for 2^30 inputs with n = 2^10.
+
+/// A node of the tree, with the inputs it commits to
+#[derive(Debug, Clone, PartialEq)]
+struct Node<E: PairingEngine> {
+    inputs: Vec<E::Fr>,
+    commitment: DynCommitment<E>,
+}
+
+/// A tree of commitments over a vector that is longer than the dimension
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynVerkleTree<E: PairingEngine> {
+    len: usize,
+    // levels[0] are the leaves, and the last level holds the root alone
+    levels: Vec<Vec<Node<E>>>,
+}
+
+/// A `DynVerkleTree` whose nodes are of dimension `N`
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerkleTree<E: PairingEngine, const N: usize> {
+    inner: DynVerkleTree<E>,
+}
+
+/// A proof of the input at a position of a tree.
+///
+/// A proof is not of constant size: it holds one G1 commitment per level below
+/// the root, i.e., `depth - 1` of them, and one G1 cross-commitment proof, so it
+/// grows logarithmically with the length of the vector.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct VerkleProof<E: PairingEngine> {
+    /// The commitments of the path from the leaf to the root, root excluded
+    pub(crate) path: Vec<E::G1Projective>,
+    /// The cross-commitment aggregated proof of the path
+    pub(crate) proof: E::G1Projective,
+}
+
+impl<E: PairingEngine> DynVerkleTree<E> {
+    /// Build the tree of a list of inputs with prover parameters
+    pub fn new(pp: &DynProverParam<E>, inputs: &[E::Fr]) -> Result<Self, PointproofError> {
+        check_min_dimension(pp.dimension(), MIN_ARITY)?;
+
+        let mut levels = Vec::new();
+        let mut level = commit_level(pp, inputs)?;
+        while level.len() > 1 {
+            let hashes: Vec<E::Fr> = level
+                .iter()
+                .map(|node| hash_node(&node.commitment))
+                .collect();
+            levels.push(level);
+            level = commit_level(pp, &hashes)?;
+        }
+        levels.push(level);
+
+        Ok(Self {
+            len: inputs.len(),
+            levels,
+        })
+    }
+
+    /// The dimension of the commitments of the tree
+    pub fn dimension(&self) -> usize {
+        self.root().n
+    }
+
+    /// The length of the vector
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the vector is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of levels of the tree
+    pub fn depth(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// The commitment at the root of the tree
+    pub fn root(&self) -> &DynCommitment<E> {
+        &self.levels[self.levels.len() - 1][0].commitment
+    }
+
+    /// Open the input at a given position
+    pub fn open(
+        &self,
+        pp: &DynProverParam<E>,
+        pos: usize,
+    ) -> Result<VerkleProof<E>, PointproofError> {
+        let n = self.dimension();
+        check_dimension(n, pp.dimension())?;
+        check_position(pos, self.len)?;
+
+        let mut commitments = Vec::with_capacity(self.depth());
+        let mut positions = Vec::with_capacity(self.depth());
+        let mut values = Vec::with_capacity(self.depth());
+        let mut proofs = Vec::with_capacity(self.depth());
+        let mut index = pos;
+        for level in self.levels.iter() {
+            let node = &level[index / n];
+            let pos = index % n;
+            let value = node.inputs[pos];
+            let witness = DynCommitment::open(pp, &node.inputs, pos)?;
+            proofs.push(
+                node.commitment
+                    .aggregate_proofs(&[pos], &[value], &[witness])?,
+            );
+            commitments.push(node.commitment);
+            positions.push(pos);
+            values.push(value);
+            index /= n;
+        }
+
+        let sets: Vec<&[usize]> = positions.iter().map(std::slice::from_ref).collect();
+        let inputs: Vec<&[E::Fr]> = values.iter().map(std::slice::from_ref).collect();
+        let proof =
+            DynCommitment::cross_commitment_aggregate(&commitments, &sets, &inputs, &proofs)?;
+        commitments.pop();
+        Ok(VerkleProof {
+            path: commitments.iter().map(|com| com.commitment).collect(),
+            proof,
+        })
+    }
+
+    /// Verify that `input` is at position `pos` of the vector of length `len`
+    /// committed to by the tree with root `root`
+    pub fn verify(
+        vp: &DynVerifierParam<E>,
+        root: &DynCommitment<E>,
+        len: usize,
+        pos: usize,
+        input: &E::Fr,
+        proof: &VerkleProof<E>,
+    ) -> Result<bool, PointproofError> {
+        let n = vp.dimension();
+        check_dimension(n, root.n)?;
+        check_min_dimension(n, MIN_ARITY)?;
+        check_position(pos, len)?;
+        check_same_length(tree_depth(len, n) - 1, proof.path.len())?;
+
+        let mut commitments: Vec<DynCommitment<E>> = proof
+            .path
+            .iter()
+            .map(|&commitment| DynCommitment { n, commitment })
+            .collect();
+        commitments.push(*root);
+
+        let mut positions = Vec::with_capacity(commitments.len());
+        let mut values = Vec::with_capacity(commitments.len());
+        let mut index = pos;
+        let mut value = *input;
+        for com in commitments.iter() {
+            positions.push(index % n);
+            values.push(value);
+            value = hash_node(com);
+            index /= n;
+        }
+
+        let sets: Vec<&[usize]> = positions.iter().map(std::slice::from_ref).collect();
+        let inputs: Vec<&[E::Fr]> = values.iter().map(std::slice::from_ref).collect();
+        DynCommitment::cross_commitment_verify(vp, &commitments, &sets, &inputs, &proof.proof)
+    }
+}
+
+impl<E: PairingEngine, const N: usize> VerkleTree<E, N> {
+    /// Build the tree of a list of inputs with prover parameters
+    pub fn new(pp: &ProverParam<E, N>, inputs: &[E::Fr]) -> Result<Self, PointproofError> {
+        Ok(Self {
+            inner: DynVerkleTree::new(&pp.inner, inputs)?,
+        })
+    }
+
+    /// The length of the vector
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Whether the vector is empty
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// The number of levels of the tree
+    pub fn depth(&self) -> usize {
+        self.inner.depth()
+    }
+
+    /// The commitment at the root of the tree
+    pub fn root(&self) -> Commitment<E, N> {
+        Commitment {
+            inner: *self.inner.root(),
+        }
+    }
+
+    /// Open the input at a given position
+    pub fn open(
+        &self,
+        pp: &ProverParam<E, N>,
+        pos: usize,
+    ) -> Result<VerkleProof<E>, PointproofError> {
+        self.inner.open(&pp.inner, pos)
+    }
+
+    /// Verify that `input` is at position `pos` of the vector of length `len`
+    /// committed to by the tree with root `root`
+    pub fn verify(
+        vp: &VerifierParam<E, N>,
+        root: &Commitment<E, N>,
+        len: usize,
+        pos: usize,
+        input: &E::Fr,
+        proof: &VerkleProof<E>,
+    ) -> Result<bool, PointproofError> {
+        DynVerkleTree::verify(&vp.inner, &root.inner, len, pos, input, proof)
+    }
+}
+
+/// Commit to the inputs in chunks of the dimension, with a single node for no inputs
+fn commit_level<E: PairingEngine>(
+    pp: &DynProverParam<E>,
+    inputs: &[E::Fr],
+) -> Result<Vec<Node<E>>, PointproofError> {
+    if inputs.is_empty() {
+        return Ok(vec![Node {
+            inputs: Vec::new(),
+            commitment: DynCommitment::commit(pp, inputs)?,
+        }]);
+    }
+
+    ark_std::cfg_chunks!(inputs, pp.dimension())
+        .map(|chunk| {
+            Ok(Node {
+                inputs: chunk.to_vec(),
+                commitment: DynCommitment::commit(pp, chunk)?,
+            })
+        })
+        .collect()
+}
+
+/// The number of levels of a tree of arity n > 1 over len inputs
+fn tree_depth(len: usize, n: usize) -> usize {
+    let mut depth = 1;
+    let mut capacity = n;
+    while capacity < len {
+        capacity = capacity.saturating_mul(n);
+        depth += 1;
+    }
+    depth
+}
+
+/// Hash the commitment of a node into an input of its parent
+fn hash_node<E: PairingEngine>(com: &DynCommitment<E>) -> E::Fr {
+    let mut buf = Vec::new();
+    com.commitment
+        .into_affine()
+        .serialize(&mut buf)
+        .expect("serializing into a vector never fails");
+    let digest = Sha512::new()
+        .chain_update(NODE_DOMAIN)
+        .chain_update(&buf)
+        .finalize();
+    E::Fr::from_le_bytes_mod_order(&digest)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::StructuredReferenceString;
+    use ark_bn254::Bn254;
+    use ark_std::rand::RngCore;
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    const NUM_TEST: usize = 10;
+
+    macro_rules! test_verkle_tree {
+        ($engine: tt, $dim: expr, $disc: tt) => {
+            let mut rng = test_rng();
+
+            let srs =
+                StructuredReferenceString::<$engine, $dim>::new_srs_for_testing(&mut rng).unwrap();
+            let prover_param: ProverParam<$engine, $dim> = (&srs).into();
+            let verifier_param: VerifierParam<$engine, $dim> = (&srs).into();
+
+            // three levels, with partial nodes at each level
+            let len = $dim * $dim * 2 + 5;
+            let message: Vec<<$engine as PairingEngine>::Fr> = (0..len)
+                .map(|_| <$engine as PairingEngine>::Fr::rand(&mut rng))
+                .collect();
+            let tree = VerkleTree::<$engine, $dim>::new(&prover_param, &message).unwrap();
+            assert_eq!(tree.len(), len);
+            assert_eq!(tree.depth(), 3);
+            let root = tree.root();
+
+            let mut positions: Vec<usize> = (0..NUM_TEST)
+                .map(|_| (rng.next_u32() as usize) % len)
+                .collect();
+            positions.extend([0, len - 1]);
+            for pos in positions {
+                let proof = tree.open(&prover_param, pos).unwrap();
+                assert_eq!(proof.path.len(), 2);
+                assert!(VerkleTree::<$engine, $dim>::verify(
+                    &verifier_param,
+                    &root,
+                    len,
+                    pos,
+                    &message[pos],
+                    &proof
+                )
+                .unwrap());
+
+                let mut buf = Vec::new();
+                proof.serialize(&mut buf).unwrap();
+                assert_eq!(
+                    proof,
+                    VerkleProof::<$engine>::deserialize(&buf[..]).unwrap()
+                );
+
+                let other = message[(pos + 1) % len];
+                assert!(!VerkleTree::<$engine, $dim>::verify(
+                    &verifier_param,
+                    &root,
+                    len,
+                    pos,
+                    &other,
+                    &proof
+                )
+                .unwrap());
+                let mut bad_proof = proof.clone();
+                bad_proof.path[1] = bad_proof.path[0];
+                assert!(!VerkleTree::<$engine, $dim>::verify(
+                    &verifier_param,
+                    &root,
+                    len,
+                    pos,
+                    &message[pos],
+                    &bad_proof
+                )
+                .unwrap());
+            }
+
+            let proof = tree.open(&prover_param, 0).unwrap();
+            assert!(matches!(
+                VerkleTree::<$engine, $dim>::verify(
+                    &verifier_param,
+                    &root,
+                    $dim,
+                    0,
+                    &message[0],
+                    &proof
+                ),
+                Err(PointproofError::LengthMismatch { .. })
+            ));
+            assert!(matches!(
+                tree.open(&prover_param, len),
+                Err(PointproofError::PositionOutOfRange { .. })
+            ));
+
+            // a vector that fits in a single node
+            let tree = VerkleTree::<$engine, $dim>::new(&prover_param, &message[..$dim]).unwrap();
+            assert_eq!(tree.depth(), 1);
+            assert_eq!(
+                tree.root(),
+                Commitment::<$engine, $dim>::commit(&prover_param, &message[..$dim]).unwrap()
+            );
+            let proof = tree.open(&prover_param, 1).unwrap();
+            assert!(VerkleTree::<$engine, $dim>::verify(
+                &verifier_param,
+                &tree.root(),
+                $dim,
+                1,
+                &message[1],
+                &proof
+            )
+            .unwrap());
+        };
+    }
+
+    #[test]
+    fn test_verkle_tree() {
+        test_verkle_tree!(Bn254, 16, "bn254");
+    }
+
+    #[test]
+    fn test_verkle_tree_arity() {
+        let mut rng = test_rng();
+        let srs = StructuredReferenceString::<Bn254, 1>::new_srs_for_testing(&mut rng).unwrap();
+        let prover_param: ProverParam<Bn254, 1> = (&srs).into();
+        let verifier_param: VerifierParam<Bn254, 1> = (&srs).into();
+        let message = vec![<Bn254 as PairingEngine>::Fr::rand(&mut rng); 2];
+
+        // nodes of a single child never reach a root
+        assert!(matches!(
+            VerkleTree::<Bn254, 1>::new(&prover_param, &message),
+            Err(PointproofError::DimensionTooSmall { n: 1, min: 2 })
+        ));
+        let root = Commitment::<Bn254, 1>::commit(&prover_param, &message[..1]).unwrap();
+        let proof = VerkleProof {
+            path: Vec::new(),
+            proof: <Bn254 as PairingEngine>::G1Projective::default(),
+        };
+        assert!(matches!(
+            VerkleTree::<Bn254, 1>::verify(&verifier_param, &root, 1, 0, &message[0], &proof),
+            Err(PointproofError::DimensionTooSmall { n: 1, min: 2 })
+        ));
+    }
+}